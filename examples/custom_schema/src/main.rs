@@ -137,7 +137,6 @@ mod tests {
     use super::*;
     use rocket::http::Status;
     use rocket::local::asynchronous::Client;
-    use rocket_okapi::openapi_get_spec;
     use serde_json::Value;
 
     #[test]
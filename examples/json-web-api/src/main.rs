@@ -159,6 +159,30 @@ mod tests {
         assert!(!spec.paths.keys().any(|k| k.contains("/hidden")));
     }
 
+    #[test]
+    fn generated_spec_contains_package_metadata() {
+        let spec = openapi_get_spec![get_all_users];
+        let contact = spec.info.contact.as_ref().unwrap();
+        assert_eq!(contact.name.as_deref(), Some("Graham Esau"));
+        assert_eq!(contact.email.as_deref(), Some("gesau@hotmail.co.uk"));
+        let license = spec.info.license.as_ref().unwrap();
+        assert_eq!(license.name, "MIT");
+        assert_eq!(
+            license.url.as_deref(),
+            Some("https://spdx.org/licenses/MIT.html")
+        );
+        // From `[package.metadata.okapi]`
+        assert_eq!(
+            spec.info.terms_of_service.as_deref(),
+            Some("https://example.com/terms")
+        );
+        assert_eq!(
+            spec.info.extensions["x-logo"]["url"],
+            "https://example.com/logo.png"
+        );
+        assert_eq!(spec.servers[0].url, "http://127.0.0.1:8000");
+    }
+
     async fn fetch_openapi_spec(client: &Client, path: &str) -> Value {
         let response = client.get(path).dispatch().await;
         assert_eq!(response.status(), Status::Ok);
@@ -10,17 +10,19 @@ use rocket::get;
 use rocket::response::stream::{ByteStream, Event, EventStream, ReaderStream, TextStream};
 use rocket::tokio::fs::File;
 use rocket::tokio::time::{self, Duration};
+use rocket_okapi::response::JsonLines;
 use rocket_okapi::settings::UrlObject;
 use rocket_okapi::{openapi, openapi_get_routes, rapidoc::*, swagger_ui::*};
 
-#[openapi]
+/// Sends a `ping` event every second.
+#[openapi(send = "String", event = "ping")]
 #[get("/event_stream")]
 // Same return type as: `EventStream![]`
 fn event_stream() -> EventStream<impl Stream<Item = Event>> {
     EventStream! {
         let mut interval = time::interval(Duration::from_secs(1));
         loop {
-            yield Event::data("ping");
+            yield Event::json(&"ping").event("ping");
             interval.tick().await;
         }
     }
@@ -54,6 +56,13 @@ fn text_stream() -> TextStream<impl Stream<Item = &'static str>> {
     TextStream(rocket::futures::stream::repeat("hi"))
 }
 
+/// Streams one JSON document per line (NDJSON).
+#[openapi]
+#[get("/json_lines")]
+fn json_lines() -> JsonLines<impl Stream<Item = String>> {
+    JsonLines(rocket::futures::stream::iter(["a", "b"].map(String::from)))
+}
+
 /// This function skips the Okapi spec entirely.
 /// So this will always allow you to use all Rocket functionally.
 /// Even when OpenAPI gives you compile errors. (you can still report errors so we can fix them)
@@ -70,7 +79,13 @@ async fn main() {
     let launch_result = rocket::build()
         .mount(
             "/",
-            openapi_get_routes![event_stream, byte_stream, reader_stream, text_stream],
+            openapi_get_routes![
+                event_stream,
+                byte_stream,
+                reader_stream,
+                text_stream,
+                json_lines
+            ],
         )
         // Skip Okapi parser to prevent compile errors.
         .mount("/", rocket::routes![stream_one])
@@ -128,12 +143,34 @@ mod tests {
         serde_json::from_str(&body).expect("valid json")
     }
 
+    #[rocket::async_test]
+    async fn json_lines_documents_item_schema() {
+        let rocket = rocket::build().mount("/", openapi_get_routes![json_lines]);
+        let client = Client::tracked(rocket).await.expect("client");
+        let spec = fetch_openapi_spec(&client, "/openapi.json").await;
+        let content = &spec["paths"]["/json_lines"]["get"]["responses"]["200"]["content"];
+        assert_eq!(content["application/x-ndjson"]["schema"]["type"], "string");
+
+        let response = client.get("/json_lines").dispatch().await;
+        assert_eq!(
+            response.content_type().map(|ct| ct.to_string()),
+            Some("application/x-ndjson".to_owned())
+        );
+        assert_eq!(response.into_string().await.unwrap(), "\"a\"\n\"b\"\n");
+    }
+
     #[rocket::async_test]
     async fn server_openapi_matches_stream_routes() {
         let rocket = rocket::build()
             .mount(
                 "/",
-                openapi_get_routes![event_stream, byte_stream, reader_stream, text_stream],
+                openapi_get_routes![
+                    event_stream,
+                    byte_stream,
+                    reader_stream,
+                    text_stream,
+                    json_lines
+                ],
             )
             .mount("/", rocket::routes![stream_one]);
         let client = Client::tracked(rocket).await.expect("client");
@@ -43,7 +43,8 @@ fn test_websocket() -> RawHtml<&'static str> {
     )
 }
 
-#[openapi]
+/// Greets the client once after connecting.
+#[openapi(send = "String")]
 #[get("/hello/<name>")]
 fn hello(ws: rocket_ws::WebSocket, name: &str) -> rocket_ws::Channel<'_> {
     ws.channel(move |mut stream| {
@@ -55,7 +56,8 @@ fn hello(ws: rocket_ws::WebSocket, name: &str) -> rocket_ws::Channel<'_> {
     })
 }
 
-#[openapi]
+/// Sends every received message back to the client.
+#[openapi(send = "String", receive = "String")]
 #[get("/echo")]
 fn echo(ws: rocket_ws::WebSocket) -> rocket_ws::Channel<'static> {
     ws.channel(move |mut stream| {
@@ -126,6 +128,19 @@ mod tests {
         serde_json::from_str(&body).expect("valid json")
     }
 
+    #[rocket::async_test]
+    async fn server_asyncapi_contains_websocket_channels() {
+        let rocket = rocket::build().mount("/", openapi_get_routes![test_websocket, hello, echo]);
+        let client = Client::tracked(rocket).await.expect("client");
+        let spec = fetch_openapi_spec(&client, "/asyncapi.json").await;
+        let channels = spec["channels"].as_object().unwrap();
+        assert_eq!(channels.len(), 2);
+        assert!(channels["/echo"]["publish"]["message"]["payload"].is_object());
+        assert!(channels["/echo"]["subscribe"]["message"]["payload"].is_object());
+        assert!(channels["/hello/{name}"]["parameters"]["name"].is_object());
+        assert!(channels["/hello/{name}"]["publish"].is_null());
+    }
+
     #[rocket::async_test]
     async fn server_openapi_contains_websocket_routes() {
         let rocket = rocket::build().mount("/", openapi_get_routes![test_websocket, hello, echo]);
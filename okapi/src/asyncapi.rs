@@ -0,0 +1,164 @@
+//! Structs for [AsyncAPI 2.6](https://www.asyncapi.com/docs/reference/specification/v2.6.0)
+//! documents, used to describe message-driven endpoints like WebSockets and Server-Sent Events.
+//!
+//! Where the objects are identical to their OpenAPI counterparts the types from
+//! [`openapi3`](crate::openapi3) are reused.
+
+use crate::openapi3::{ExternalDocs, Info, Object, RefOr, SchemaObject, ServerVariable, Tag};
+use crate::Map;
+#[cfg(feature = "impl_json_schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+impl AsyncApi {
+    pub fn new() -> Self {
+        AsyncApi {
+            asyncapi: Self::default_version(),
+            ..Default::default()
+        }
+    }
+
+    pub fn default_version() -> String {
+        "2.6.0".to_owned()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Default)]
+#[cfg_attr(feature = "impl_json_schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct AsyncApi {
+    pub asyncapi: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub info: Info,
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub servers: Map<String, Server>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_content_type: Option<String>,
+    pub channels: Map<String, ChannelItem>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub components: Option<Components>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<Tag>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_docs: Option<ExternalDocs>,
+    #[serde(flatten)]
+    pub extensions: Object,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, Default)]
+#[cfg_attr(feature = "impl_json_schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Server {
+    pub url: String,
+    pub protocol: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protocol_version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub variables: Map<String, ServerVariable>,
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub bindings: Object,
+    #[serde(flatten)]
+    pub extensions: Object,
+}
+
+/// Describes a channel. In AsyncAPI 2.x the operations are seen from the point of view of the
+/// client: `subscribe` lists the messages the application *sends*, `publish` the messages the
+/// application *receives*.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Default)]
+#[cfg_attr(feature = "impl_json_schema", derive(JsonSchema))]
+#[serde(default, rename_all = "camelCase")]
+pub struct ChannelItem {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    pub parameters: Map<String, RefOr<Parameter>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subscribe: Option<Operation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub publish: Option<Operation>,
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    pub bindings: Object,
+    #[serde(flatten)]
+    pub extensions: Object,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Default)]
+#[cfg_attr(feature = "impl_json_schema", derive(JsonSchema))]
+#[serde(default, rename_all = "camelCase")]
+pub struct Parameter {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<SchemaObject>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    #[serde(flatten)]
+    pub extensions: Object,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Default)]
+#[cfg_attr(feature = "impl_json_schema", derive(JsonSchema))]
+#[serde(default, rename_all = "camelCase")]
+pub struct Operation {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operation_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<Tag>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_docs: Option<ExternalDocs>,
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    pub bindings: Object,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<RefOr<Message>>,
+    #[serde(flatten)]
+    pub extensions: Object,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Default)]
+#[cfg_attr(feature = "impl_json_schema", derive(JsonSchema))]
+#[serde(default, rename_all = "camelCase")]
+pub struct Message {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headers: Option<SchemaObject>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payload: Option<SchemaObject>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<Tag>,
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    pub bindings: Object,
+    #[serde(flatten)]
+    pub extensions: Object,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Default)]
+#[cfg_attr(feature = "impl_json_schema", derive(JsonSchema))]
+#[serde(default, rename_all = "camelCase")]
+pub struct Components {
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    pub schemas: Map<String, SchemaObject>,
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    pub messages: Map<String, RefOr<Message>>,
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    pub parameters: Map<String, RefOr<Parameter>>,
+    #[serde(flatten)]
+    pub extensions: Object,
+}
@@ -4,6 +4,7 @@
 pub type Map<K, V> = indexmap::IndexMap<K, V>;
 pub type MapEntry<'a, K, V> = indexmap::map::Entry<'a, K, V>;
 
+pub mod asyncapi;
 pub mod merge;
 pub mod openapi3;
 
@@ -81,20 +81,24 @@ fn test_merge_tags_and_tag_merge() {
 #[test]
 fn test_merge_paths_prefix_and_methods() {
     let mut p1: Map<String, PathItem> = Map::new();
-    let mut pi1 = PathItem::default();
-    pi1.get = Some(Operation {
-        responses: Responses::default(),
+    let pi1 = PathItem {
+        get: Some(Operation {
+            responses: Responses::default(),
+            ..Default::default()
+        }),
         ..Default::default()
-    });
+    };
     // Use a path with the prefix already included so merge can find an existing entry
     p1.insert("/base/x".to_owned(), pi1);
 
     let mut p2: Map<String, PathItem> = Map::new();
-    let mut pi2 = PathItem::default();
-    pi2.post = Some(Operation {
-        responses: Responses::default(),
+    let pi2 = PathItem {
+        post: Some(Operation {
+            responses: Responses::default(),
+            ..Default::default()
+        }),
         ..Default::default()
-    });
+    };
     p2.insert("/x".to_owned(), pi2);
 
     merge_paths(&mut p1, &"/base/", &p2).unwrap();
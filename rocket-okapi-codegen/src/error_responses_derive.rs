@@ -0,0 +1,188 @@
+//! Implementation of `#[derive(OpenApiErrorResponses)]`, which implements `OpenApiResponderInner`
+//! for an error enum with its own response for every variant, using `#[error_response(...)]`
+//! attributes.
+
+use crate::openapi_attr::doc_attr::get_description_from_doc;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{Attribute, Data, DeriveInput, Error, LitInt, LitStr, Result, Type};
+
+/// The response of the variants with the same status code.
+struct StatusResponse {
+    status: u16,
+    /// The distinct doc comments of the variants.
+    descriptions: Vec<String>,
+    /// The distinct bodies of the variants, by content type and schema type.
+    content: Vec<((String, String), Type)>,
+}
+
+/// The `#[error_response(status = ..., content_type = "...", schema = T)]` attribute of an enum or
+/// a variant.
+#[derive(Default)]
+struct ErrorResponseAttrs {
+    status: Option<u16>,
+    content_type: Option<String>,
+    schema: Option<Type>,
+}
+
+pub fn derive(input: DeriveInput) -> Result<TokenStream2> {
+    let Data::Enum(data) = &input.data else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "`OpenApiErrorResponses` can only be derived for enums.",
+        ));
+    };
+    let defaults = parse_attrs(&input.attrs)?;
+    if defaults.status.is_some() {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "The `status` has to be set on every variant.",
+        ));
+    }
+
+    // The variants are grouped by status code, so variants with the same status code share one
+    // response: their descriptions are combined and every body is only documented once.
+    let mut statuses: Vec<StatusResponse> = Vec::new();
+    for variant in &data.variants {
+        let attrs = parse_attrs(&variant.attrs)?;
+        let Some(status) = attrs.status else {
+            return Err(Error::new_spanned(
+                &variant.ident,
+                "Missing `#[error_response(status = ...)]`.",
+            ));
+        };
+        let index = match statuses
+            .iter()
+            .position(|response| response.status == status)
+        {
+            Some(index) => index,
+            None => {
+                statuses.push(StatusResponse {
+                    status,
+                    descriptions: Vec::new(),
+                    content: Vec::new(),
+                });
+                statuses.len() - 1
+            }
+        };
+        let response = &mut statuses[index];
+        if let Some(description) = get_description_from_doc(&variant.attrs) {
+            if !response.descriptions.contains(&description) {
+                response.descriptions.push(description);
+            }
+        }
+        if let Some(schema) = attrs.schema.as_ref().or(defaults.schema.as_ref()) {
+            let content_type = attrs
+                .content_type
+                .as_ref()
+                .or(defaults.content_type.as_ref())
+                .map_or("application/json", String::as_str)
+                .to_owned();
+            let key = (content_type, quote!(#schema).to_string());
+            if !response.content.iter().any(|(other, _)| *other == key) {
+                response.content.push((key, schema.clone()));
+            }
+        }
+    }
+
+    let variant_responses = statuses.iter().map(|response| {
+        let status = response.status;
+        let description = match response.descriptions.as_slice() {
+            [description] => description.clone(),
+            descriptions => descriptions
+                .iter()
+                .map(|description| format!("- {description}"))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        };
+        let content = response.content.iter().map(|((content_type, _), schema)| {
+            quote! { (#content_type, gen.json_schema::<#schema>()) }
+        });
+        quote! {
+            ::rocket_okapi::util::add_described_response(
+                &mut responses,
+                #status,
+                #description,
+                vec![#(#content),*],
+            )?;
+        }
+    });
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::rocket_okapi::response::OpenApiResponderInner
+            for #ident #ty_generics #where_clause
+        {
+            fn responses(
+                gen: &mut ::rocket_okapi::gen::OpenApiGenerator,
+            ) -> ::rocket_okapi::Result<::rocket_okapi::okapi::openapi3::Responses> {
+                let mut responses = ::rocket_okapi::okapi::openapi3::Responses::default();
+                #(#variant_responses)*
+                Ok(responses)
+            }
+        }
+    })
+}
+
+/// Parse the `#[error_response(...)]` attributes. The content type can be a media type or a
+/// shorthand like `json`, like in Rocket.
+fn parse_attrs(attrs: &[Attribute]) -> Result<ErrorResponseAttrs> {
+    let mut error_attrs = ErrorResponseAttrs::default();
+    for attr in attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("error_response"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("status") {
+                let status: LitInt = meta.value()?.parse()?;
+                error_attrs.status = Some(status.base10_parse()?);
+            } else if meta.path.is_ident("content_type") {
+                let content_type: LitStr = meta.value()?.parse()?;
+                let parsed = rocket_http::ContentType::parse_flexible(&content_type.value())
+                    .ok_or_else(|| Error::new_spanned(&content_type, "Invalid content type."))?;
+                error_attrs.content_type = Some(parsed.to_string());
+            } else if meta.path.is_ident("schema") {
+                error_attrs.schema = Some(meta.value()?.parse()?);
+            } else {
+                return Err(meta.error("Expected `status`, `content_type` or `schema`."));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(error_attrs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_parse_attrs() {
+        let attrs: Vec<Attribute> = vec![parse_quote!(
+            #[error_response(status = 409, content_type = "json", schema = Vec<Conflict>)]
+        )];
+        let error_attrs = parse_attrs(&attrs).unwrap();
+        assert_eq!(error_attrs.status, Some(409));
+        assert_eq!(
+            error_attrs.content_type.as_deref(),
+            Some("application/json")
+        );
+        assert!(error_attrs.schema.is_some());
+        let attrs: Vec<Attribute> = vec![parse_quote!(#[error_response(code = 409)])];
+        assert!(parse_attrs(&attrs).is_err());
+    }
+
+    #[test]
+    fn test_every_variant_needs_a_status() {
+        let input: DeriveInput = parse_quote! {
+            enum MyError {
+                #[error_response(status = 404)]
+                NotFound,
+                Other,
+            }
+        };
+        assert!(derive(input).is_err());
+    }
+}
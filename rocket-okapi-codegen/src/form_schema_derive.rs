@@ -0,0 +1,263 @@
+//! Implementation of `#[derive(FormSchema)]`, which implements `JsonSchema` for a form using
+//! Rocket's `#[field(...)]` attributes: the names of the fields, their validators and defaults.
+
+use crate::openapi_attr::doc_attr::get_description_from_doc;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::{
+    parse_quote, Attribute, Data, DeriveInput, Error, Expr, Fields, GenericArgument, GenericParam,
+    Lit, PathArguments, Result, Type,
+};
+
+/// The information of a field that is read from its `#[field(...)]` attributes.
+#[derive(Default)]
+struct FieldAttrs {
+    name: Option<String>,
+    validators: Vec<Expr>,
+    has_default: bool,
+}
+
+pub fn derive(mut input: DeriveInput) -> Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    &input.ident,
+                    "`FormSchema` can only be derived for structs with named fields.",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "`FormSchema` can only be derived for structs.",
+            ))
+        }
+    };
+
+    let mut field_schemas = Vec::new();
+    for field in fields {
+        let attrs = parse_field_attrs(&field.attrs)?;
+        let ty = &field.ty;
+        let name = match attrs.name {
+            Some(name) => name,
+            None => field
+                .ident
+                .as_ref()
+                .expect("named field")
+                .unraw()
+                .to_string(),
+        };
+        let description = get_description_from_doc(&field.attrs);
+        let set_description = match description {
+            Some(description) => quote! {
+                schema.insert("description".to_owned(), #description.into());
+            },
+            None => quote! {},
+        };
+        let validators = attrs.validators.iter().filter_map(create_validator);
+        let required = if attrs.has_default {
+            quote! { false }
+        } else {
+            quote! { ::rocket_okapi::request::form_schema::form_field_is_required::<#ty>() }
+        };
+        let schema = match file_schema(ty) {
+            Some(schema) => schema,
+            None => quote! { generator.subschema_for::<#ty>() },
+        };
+        field_schemas.push(quote! {{
+            let mut schema = #schema;
+            #set_description
+            #(#validators)*
+            (#name.to_owned(), schema, #required)
+        }});
+    }
+
+    let description = match get_description_from_doc(&input.attrs) {
+        Some(description) => quote! { Some(#description) },
+        None => quote! { None },
+    };
+    let ident = &input.ident;
+    let schema_name = ident.to_string();
+    let schema_id = quote! { concat!(module_path!(), "::", #schema_name) };
+    // Every type parameter has to be documented as well.
+    for param in &mut input.generics.params {
+        if let GenericParam::Type(param) = param {
+            param
+                .bounds
+                .push(parse_quote!(::rocket_okapi::okapi::schemars::JsonSchema));
+        }
+    }
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::rocket_okapi::okapi::schemars::JsonSchema for #ident #ty_generics #where_clause {
+            fn schema_name() -> ::std::borrow::Cow<'static, str> {
+                #schema_name.into()
+            }
+
+            fn schema_id() -> ::std::borrow::Cow<'static, str> {
+                #schema_id.into()
+            }
+
+            fn json_schema(
+                generator: &mut ::rocket_okapi::okapi::schemars::SchemaGenerator,
+            ) -> ::rocket_okapi::okapi::schemars::Schema {
+                let fields = vec![#(#field_schemas),*];
+                ::rocket_okapi::request::form_schema::form_struct_schema(#description, fields)
+            }
+        }
+    })
+}
+
+/// Parse the `#[field(name = "...", validate = ..., default = ...)]` attributes of a field.
+/// Only the first `name` is used, Rocket accepts the others as well.
+fn parse_field_attrs(attrs: &[Attribute]) -> Result<FieldAttrs> {
+    let mut field_attrs = FieldAttrs::default();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("field")) {
+        attr.parse_nested_meta(|meta| {
+            let value: Expr = meta.value()?.parse()?;
+            if meta.path.is_ident("name") {
+                if field_attrs.name.is_none() {
+                    field_attrs.name = Some(string_in_expr(&value).ok_or_else(|| {
+                        Error::new_spanned(&value, "Expected a string as field name.")
+                    })?);
+                }
+            } else if meta.path.is_ident("validate") {
+                field_attrs.validators.push(value);
+            } else if meta.path.is_ident("default") || meta.path.is_ident("default_with") {
+                field_attrs.has_default = true;
+            }
+            Ok(())
+        })?;
+    }
+    Ok(field_attrs)
+}
+
+/// Get the name of `name = "..."` or `name = uncased("...")`.
+fn string_in_expr(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Lit(expr) => match &expr.lit {
+            Lit::Str(name) => Some(name.value()),
+            _ => None,
+        },
+        Expr::Call(call) => call.args.first().and_then(string_in_expr),
+        Expr::Paren(expr) => string_in_expr(&expr.expr),
+        _ => None,
+    }
+}
+
+/// Create the code that documents a validator, `None` if it can not be documented.
+/// Validators can be chained with methods like `.or_else(msg!("..."))`, these are ignored.
+fn create_validator(validator: &Expr) -> Option<TokenStream2> {
+    let mut expr = validator;
+    while let Expr::MethodCall(call) = expr {
+        expr = &call.receiver;
+    }
+    let Expr::Call(call) = expr else {
+        return None;
+    };
+    let Expr::Path(func) = &*call.func else {
+        return None;
+    };
+    let func = func.path.segments.last()?.ident.to_string();
+    // Arguments can refer to other fields with `self`, which is not available here.
+    let arg = call.args.first()?;
+    if call.args.len() != 1 || quote!(#arg).to_string().split(' ').any(|t| t == "self") {
+        return None;
+    }
+    let set_validation = match func.as_str() {
+        "len" => quote! { set_len_validation },
+        "range" => quote! { set_range_validation },
+        "one_of" => quote! { set_contains_validation },
+        "contains" => {
+            return Some(quote! {
+                ::rocket_okapi::request::form_schema::set_contains_validation(&mut schema, [#arg]);
+            })
+        }
+        "eq" => quote! { set_eq_validation },
+        "ext" => {
+            return Some(quote! {
+                ::rocket_okapi::request::form_schema::set_ext_validation(&mut schema, &(#arg));
+            })
+        }
+        _ => return None,
+    };
+    Some(quote! {
+        ::rocket_okapi::request::form_schema::#set_validation(&mut schema, #arg);
+    })
+}
+
+/// The schema of file fields: `TempFile`, `Capped<TempFile>`, optionally in an `Option` or `Vec`.
+/// `TempFile` does not implement `JsonSchema`, so these can not use the generator.
+fn file_schema(ty: &Type) -> Option<TokenStream2> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    let inner = match &segment.arguments {
+        PathArguments::AngleBracketed(args) => args.args.iter().find_map(|arg| match arg {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        }),
+        _ => None,
+    };
+    match segment.ident.to_string().as_str() {
+        "TempFile" => Some(quote! { ::rocket_okapi::request::form_schema::file_schema() }),
+        "Capped" | "Option" => file_schema(inner?),
+        "Vec" => {
+            file_schema(inner?)?;
+            Some(quote! { ::rocket_okapi::request::form_schema::file_array_schema() })
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_field_attrs() {
+        let attrs: Vec<Attribute> = vec![
+            parse_quote!(#[field(name = uncased("user-name"), validate = len(1..50))]),
+            parse_quote!(#[field(name = "other", default = 3)]),
+            parse_quote!(#[field(validate = range(1..).or_else(msg!("too small")))]),
+        ];
+        let field_attrs = parse_field_attrs(&attrs).unwrap();
+        assert_eq!(field_attrs.name.as_deref(), Some("user-name"));
+        assert_eq!(field_attrs.validators.len(), 2);
+        assert!(field_attrs.has_default);
+    }
+
+    #[test]
+    fn test_file_schema() {
+        let ty: Type = parse_quote!(Option<Capped<TempFile<'r>>>);
+        assert!(file_schema(&ty)
+            .unwrap()
+            .to_string()
+            .contains("file_schema"));
+        let ty: Type = parse_quote!(Vec<TempFile<'r>>);
+        assert!(file_schema(&ty)
+            .unwrap()
+            .to_string()
+            .contains("file_array_schema"));
+        let ty: Type = parse_quote!(Vec<String>);
+        assert!(file_schema(&ty).is_none());
+    }
+
+    #[test]
+    fn test_create_validator() {
+        let validator: Expr = parse_quote!(range(1..).or_else(msg!("too small")));
+        let tokens = create_validator(&validator).unwrap().to_string();
+        assert!(tokens.contains("set_range_validation"));
+        let validator: Expr = parse_quote!(neq(self.other));
+        assert!(create_validator(&validator).is_none());
+        let validator: Expr = parse_quote!(ext(ContentType::PNG));
+        let tokens = create_validator(&validator).unwrap().to_string();
+        assert!(tokens.contains("set_ext_validation"));
+        let validator: Expr = parse_quote!(with(|v| v.is_empty(), "empty"));
+        assert!(create_validator(&validator).is_none());
+    }
+}
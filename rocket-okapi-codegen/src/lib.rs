@@ -6,12 +6,25 @@
 //! - `#[openapi]`: To generate the documentation for an endpoint/route.
 //! - `openapi_routes![...]`: Returns a closure for generating routes.
 //! - `openapi_spec![...]`: Returns a closure for generating OpenApi objects.
+//! - `asyncapi_spec![...]`: Returns a closure for generating AsyncApi objects.
+//! - `openapi_catchers![...]`: Returns the documentation of catchers for `OpenApiSettings`.
 //! - `#[derive(OpenApiFromRequest)]`: Implement `OpenApiFromRequest` trait for a given struct.
+//! - `#[derive(FormSchema)]`: Implement `JsonSchema` for a form using Rocket's `field`
+//!   attributes.
+//! - `#[derive(OpenApiResponder)]`: Implement `OpenApiResponderInner` for a type using Rocket's
+//!   `response` attributes.
+//! - `#[derive(OpenApiErrorResponses)]`: Implement `OpenApiResponderInner` for an error enum with a
+//!   response for every variant.
 //!
 
+mod error_responses_derive;
+mod form_schema_derive;
 mod openapi_attr;
 mod openapi_spec;
+mod package_metadata;
+mod parse_catchers;
 mod parse_routes;
+mod responder_derive;
 
 use proc_macro::TokenStream;
 use quote::quote;
@@ -32,11 +45,12 @@ use syn::Ident;
 /// }
 /// ```
 #[proc_macro_attribute]
-pub fn openapi(args: TokenStream, mut input: TokenStream) -> TokenStream {
-    // We don't need to modify/replace the input TokenStream,
-    // we just need to append to it.
-    input.extend(openapi_attr::parse(args, input.clone()));
-    input
+pub fn openapi(args: TokenStream, input: TokenStream) -> TokenStream {
+    // We only need to remove the doc comments of the arguments from the input TokenStream,
+    // everything else is appended to it.
+    let mut output = openapi_attr::strip_arg_docs(input.clone());
+    output.extend(openapi_attr::parse(args, input));
+    output
 }
 
 /// Generate and return a closure that can be used to generate the routes.
@@ -85,6 +99,76 @@ pub fn openapi_spec(input: TokenStream) -> TokenStream {
     .into()
 }
 
+/// Generate and return a closure that can be used to generate the OpenAPI and the AsyncAPI
+/// specification at once. The routes are only documented once for both.
+///
+/// This closure take 1 argument:
+/// - `settings`: `rocket_okapi::settings::OpenApiSettings`
+///
+/// It returns a `rocket_okapi::okapi::openapi3::OpenApi` and an
+/// `Option<rocket_okapi::okapi::asyncapi::AsyncApi>`, which is `None` when no route declares
+/// `send` or `receive` message types.
+///
+/// Example:
+/// ```rust,ignore
+/// let settings = rocket_okapi::settings::OpenApiSettings::new();
+/// let (openapi, asyncapi) =
+///     rocket_okapi::openapi_and_asyncapi_spec![get_message, echo_channel](&settings);
+/// ```
+#[proc_macro]
+pub fn openapi_and_asyncapi_spec(input: TokenStream) -> TokenStream {
+    let spec = openapi_spec::create_openapi_and_asyncapi_spec(input)
+        .unwrap_or_else(|e| e.to_compile_error());
+    (quote! {
+        #spec
+    })
+    .into()
+}
+
+/// Generate and return a closure that can be used to generate the AsyncAPI specification.
+///
+/// This closure take 1 argument:
+/// - `settings`: `rocket_okapi::settings::OpenApiSettings`
+///
+/// It returns `rocket_okapi::okapi::asyncapi::AsyncApi`.
+///
+/// Only routes that declare `send` or `receive` message types in `#[openapi]` are added to the
+/// AsyncAPI specification.
+///
+/// Example:
+/// ```rust,ignore
+/// let settings = rocket_okapi::settings::OpenApiSettings::new();
+/// let spec = rocket_okapi::asyncapi_spec![echo_channel, ticker_events](&settings);
+/// ```
+#[proc_macro]
+pub fn asyncapi_spec(input: TokenStream) -> TokenStream {
+    let spec = openapi_spec::create_asyncapi_spec(input).unwrap_or_else(|e| e.to_compile_error());
+    (quote! {
+        #spec
+    })
+    .into()
+}
+
+/// Generate a list of `rocket_okapi::settings::OpenApiCatcher`s for the given Rocket catchers,
+/// to be set as `OpenApiSettings::catchers`. The catchers must be marked with `#[openapi]`.
+/// The catchers still have to be registered in Rocket with `rocket::catchers![...]`.
+///
+/// Example:
+/// ```rust,ignore
+/// let settings = rocket_okapi::settings::OpenApiSettings {
+///     catchers: rocket_okapi::openapi_catchers![not_found, default_catcher],
+///     ..Default::default()
+/// };
+/// ```
+#[proc_macro]
+pub fn openapi_catchers(input: TokenStream) -> TokenStream {
+    let catchers = parse_catchers::parse_catchers(input).unwrap_or_else(|e| e.to_compile_error());
+    (quote! {
+        #catchers
+    })
+    .into()
+}
+
 /// Derive marco for the `OpenApiFromRequest` trait.
 ///
 /// This derive trait is a very simple implementation for anything that does not
@@ -136,6 +220,138 @@ pub fn open_api_from_request_derive(input: TokenStream) -> TokenStream {
     gen.into()
 }
 
+/// Derive macro that implements `JsonSchema` for a struct that derives Rocket's `FromForm`, so it
+/// is documented as a query or form data by `OpenApiFromForm`. Use it instead of
+/// `#[derive(JsonSchema)]`.
+///
+/// The schema uses Rocket's `#[field(...)]` attributes instead of the `serde` ones:
+/// - `name = "..."` or `name = uncased("...")`: The name of the field.
+/// - `validate = ...`: `len(..)`, `range(..)`, `one_of(..)`, `contains(..)` and `eq(..)` are
+///   documented as `minLength`/`maxLength` (`minItems`/`maxItems` for sequences),
+///   `minimum`/`maximum`, `contains` and `enum`. The content type of `ext(..)` is used for the
+///   `Encoding` of files. Other validators are ignored.
+/// - `default = ...` and `default_with = ...`: The field is not required.
+///
+/// `TempFile` fields (also in `Capped`, `Option` or `Vec`) are documented as binary strings.
+///
+/// Doc comments are used as descriptions.
+/// ### Example
+/// ```rust,ignore
+/// use rocket::form::FromForm;
+/// use rocket_okapi::request::FormSchema;
+///
+/// #[derive(FromForm, FormSchema)]
+/// pub struct Search<'r> {
+///     /// The text to search for.
+///     #[field(name = "q", validate = len(1..50))]
+///     query: &'r str,
+///     #[field(validate = range(1..=100), default = 20)]
+///     limit: u32,
+/// }
+/// ```
+#[proc_macro_derive(FormSchema, attributes(field))]
+pub fn form_schema_derive(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    form_schema_derive::derive(input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+/// Derive macro that implements `OpenApiResponderInner` for a struct or enum that derives
+/// Rocket's `Responder`.
+///
+/// It reads the same attributes as Rocket:
+/// - `#[response(status = ...)]`: All responses of the responder get this status code.
+/// - `#[response(content_type = "...")]`: The content type of the successful responses, a media
+///   type or a shorthand like `json`.
+/// - The first field is the responder, the other fields are documented as headers of the
+///   successful responses, unless they have `#[response(ignore)]`. A header is named after its
+///   field (`x_request_id` becomes `X-Request-Id`), after its type for tuple fields, and
+///   `ContentType` fields are `Content-Type`. The name of a `Header` in a tuple field is only known
+///   at runtime, so it is not documented.
+///
+/// Doc comments of the struct or variants describe their successful responses, doc comments of
+/// header fields describe the header.
+/// ### Example
+/// ```rust,ignore
+/// use rocket::http::Header;
+/// use rocket::serde::json::Json;
+/// use rocket::Responder;
+/// use rocket_okapi::response::OpenApiResponder;
+///
+/// #[derive(Responder, OpenApiResponder)]
+/// pub enum ApiError {
+///     /// The user does not exist.
+///     #[response(status = 404, content_type = "json")]
+///     NotFound(Json<Message>),
+///     /// Too many requests were made.
+///     #[response(status = 429)]
+///     TooManyRequests {
+///         message: String,
+///         /// Seconds to wait before the next request.
+///         retry_after: Header<'static>,
+///     },
+/// }
+/// ```
+#[proc_macro_derive(OpenApiResponder, attributes(response))]
+pub fn open_api_responder_derive(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    responder_derive::derive(input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+/// Derive macro that implements `OpenApiResponderInner` for an error enum, with its own response
+/// for every variant. Use it for errors that implement `Responder` themselves, so that
+/// `Result<Json<T>, MyError>` documents every error status code separately.
+///
+/// Every variant needs an `#[error_response(status = ...)]` attribute, the status code of the
+/// variant. Variants can have the same status code: they share one response, which lists the
+/// descriptions of the variants and documents every distinct body once. The body of the
+/// responses is set with:
+/// - `schema = T`: The schema of the body, by default none. It can be set on the enum for all
+///   variants and on a variant to override it.
+/// - `content_type = "..."`: The content type of the body, a media type or a shorthand like
+///   `json`. By default `application/json`.
+///
+/// Doc comments of the variants are used as the descriptions of the responses.
+/// ### Example
+/// ```rust,ignore
+/// use rocket_okapi::response::OpenApiErrorResponses;
+///
+/// #[derive(Debug, OpenApiErrorResponses)]
+/// #[error_response(schema = ErrorMessage)]
+/// pub enum MyError {
+///     /// The request is invalid.
+///     #[error_response(status = 400)]
+///     BadRequest(String),
+///     /// The user does not exist.
+///     #[error_response(status = 404)]
+///     NotFound,
+///     /// The user already exists.
+///     #[error_response(status = 409, schema = ConflictMessage)]
+///     Conflict(ConflictMessage),
+/// }
+///
+/// impl<'r> Responder<'r, 'static> for MyError {
+///     // ...
+/// }
+/// ```
+#[proc_macro_derive(OpenApiErrorResponses, attributes(error_response))]
+pub fn open_api_error_responses_derive(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    error_responses_derive::derive(input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+fn get_add_catcher_fn_name(catcher_fn_name: &Ident) -> Ident {
+    Ident::new(
+        &format!("okapi_add_catcher_for_{catcher_fn_name}_"),
+        catcher_fn_name.span(),
+    )
+}
+
 fn get_add_operation_fn_name(route_fn_name: &Ident) -> Ident {
     Ident::new(
         &format!("okapi_add_operation_for_{route_fn_name}_"),
@@ -0,0 +1,35 @@
+use syn::{Attribute, Error, Ident, LitInt};
+
+/// Parse the status code of a `#[catch(404)]` attribute, `None` for `#[catch(default)]`.
+/// Returns `None` if there is no `catch` attribute, so the function is not a catcher.
+pub fn parse_catch_attr(attrs: &[Attribute]) -> Option<Result<Option<u16>, Error>> {
+    let attr = attrs.iter().find(|a| a.path().is_ident("catch"))?;
+    if let Ok(status) = attr.parse_args::<LitInt>() {
+        return Some(status.base10_parse::<u16>().map(Some));
+    }
+    Some(match attr.parse_args::<Ident>() {
+        Ok(ident) if ident == "default" => Ok(None),
+        _ => Err(Error::new_spanned(
+            attr,
+            "Expected a status code or `default` in the `catch` attribute.",
+        )),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_parse_catch_attr() {
+        let attrs: Vec<Attribute> = vec![parse_quote!(#[catch(404)])];
+        assert_eq!(parse_catch_attr(&attrs).unwrap().unwrap(), Some(404));
+        let attrs: Vec<Attribute> = vec![parse_quote!(#[catch(default)])];
+        assert_eq!(parse_catch_attr(&attrs).unwrap().unwrap(), None);
+        let attrs: Vec<Attribute> = vec![parse_quote!(#[catch(other)])];
+        assert!(parse_catch_attr(&attrs).unwrap().is_err());
+        let attrs: Vec<Attribute> = vec![parse_quote!(#[get("/")])];
+        assert!(parse_catch_attr(&attrs).is_none());
+    }
+}
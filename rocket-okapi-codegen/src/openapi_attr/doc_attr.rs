@@ -1,11 +1,166 @@
 use syn::{Attribute, Meta::NameValue, MetaNameValue};
 
 pub fn get_title_and_desc_from_doc(attrs: &[Attribute]) -> (Option<String>, Option<String>) {
+    match get_doc(attrs) {
+        None => (None, None),
+        Some(doc) => split_title_and_desc(&doc),
+    }
+}
+
+/// The documentation of a route, from its doc comment.
+#[derive(Debug, Default, PartialEq)]
+pub struct RouteDocs {
+    pub title: Option<String>,
+    /// The description, without the items of the sections below and the `# Deprecated` section.
+    pub description: Option<String>,
+    /// The items of the `# Parameters` (or `# Arguments`) section: `* name - text`.
+    pub parameters: Vec<(String, String)>,
+    /// The items of the `# Errors` and `# Responses` sections: `* 404 - text`.
+    pub responses: Vec<(u16, String)>,
+    /// The text of the `# Deprecated` section, which can be empty.
+    pub deprecated: Option<String>,
+}
+
+/// The sections of a route doc comment that are not part of the description.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Section {
+    Parameters,
+    Responses,
+    Deprecated,
+}
+
+impl Section {
+    fn from_heading(line: &str) -> Option<Self> {
+        if !line.starts_with('#') {
+            return None;
+        }
+        match line.trim_start_matches('#').trim().to_lowercase().as_str() {
+            "parameters" | "arguments" => Some(Self::Parameters),
+            "errors" | "responses" => Some(Self::Responses),
+            "deprecated" => Some(Self::Deprecated),
+            _ => None,
+        }
+    }
+}
+
+/// Splits the doc comment of a route into the title, the description and the `# Parameters`,
+/// `# Errors`, `# Responses` and `# Deprecated` sections.
+///
+/// The lines of a section that are not list items stay in the description, under the heading of
+/// the section. Headings in fenced code blocks are not sections.
+pub fn get_route_docs(attrs: &[Attribute]) -> RouteDocs {
     let doc = match get_doc(attrs) {
-        None => return (None, None),
+        None => return RouteDocs::default(),
         Some(doc) => doc,
     };
+    // The lines of the doc comment, split at the headings of the sections. The first line of a
+    // section is its heading.
+    let mut blocks: Vec<(Option<Section>, Vec<&str>)> = vec![(None, Vec::new())];
+    let mut in_code_block = false;
+    for line in doc.lines() {
+        if is_fence(line) {
+            in_code_block = !in_code_block;
+        } else if !in_code_block && line.starts_with('#') {
+            let section = Section::from_heading(line);
+            if section.is_some() || blocks.last().is_some_and(|(s, _)| s.is_some()) {
+                blocks.push((section, Vec::new()));
+            }
+        }
+        if let Some((_, lines)) = blocks.last_mut() {
+            lines.push(line);
+        }
+    }
+
+    // A doc comment that starts with a section has no title.
+    let has_title = !blocks[0].1.is_empty();
+    let mut docs = RouteDocs::default();
+    let mut main = Vec::new();
+    for (section, lines) in blocks {
+        let Some(section) = section else {
+            main.extend(lines);
+            continue;
+        };
+        let (heading, lines) = (lines[0], &lines[1..]);
+        let prose = match section {
+            Section::Parameters => {
+                let (items, prose) = list_items(lines, |name| Some(name.to_owned()));
+                docs.parameters.extend(items);
+                prose
+            }
+            Section::Responses => {
+                let (items, prose) = list_items(lines, |status| status.parse().ok());
+                docs.responses.extend(items);
+                prose
+            }
+            Section::Deprecated => {
+                let text = merge_description_lines(&lines.join("\n")).unwrap_or_default();
+                docs.deprecated = Some(text);
+                Vec::new()
+            }
+        };
+        if prose.iter().any(|line| !line.trim().is_empty()) {
+            main.push(heading);
+            main.extend(prose);
+        }
+    }
+
+    let main = main.join("\n");
+    (docs.title, docs.description) = if has_title {
+        split_title_and_desc(main.trim())
+    } else {
+        (None, merge_description_lines(&main))
+    };
+    docs
+}
 
+/// The items of a list like `* name - text`, with a name that `parse_name` accepts, and the
+/// other lines. The name can be in backticks and the text can continue on the next lines.
+fn list_items<'a, T>(
+    lines: &[&'a str],
+    parse_name: impl Fn(&str) -> Option<T>,
+) -> (Vec<(T, String)>, Vec<&'a str>) {
+    let mut items: Vec<(T, String)> = Vec::new();
+    let mut prose = Vec::new();
+    // If the previous line is a line of the last item.
+    let mut in_item = false;
+    let mut in_code_block = false;
+    for &line in lines {
+        let trimmed = line.trim();
+        if is_fence(line) {
+            in_code_block = !in_code_block;
+        } else if !in_code_block {
+            let list_item = trimmed
+                .strip_prefix("* ")
+                .or_else(|| trimmed.strip_prefix("- "));
+            let item = list_item
+                .and_then(|item| item.split_once(" - "))
+                .and_then(|(name, text)| Some((parse_name(name.trim().trim_matches('`'))?, text)));
+            if let Some((name, text)) = item {
+                items.push((name, text.trim().to_owned()));
+                in_item = true;
+                continue;
+            }
+            // Other list items are kept, like the items with a name that is not accepted.
+            let continues_item = in_item && list_item.is_none() && !trimmed.is_empty();
+            if let Some((_, text)) = items.last_mut().filter(|_| continues_item) {
+                text.push(' ');
+                text.push_str(trimmed);
+                continue;
+            }
+        }
+        in_item = false;
+        prose.push(line);
+    }
+    (items, prose)
+}
+
+/// If the line starts or ends a fenced code block.
+fn is_fence(line: &str) -> bool {
+    let line = line.trim_start();
+    line.starts_with("```") || line.starts_with("~~~")
+}
+
+fn split_title_and_desc(doc: &str) -> (Option<String>, Option<String>) {
     if doc.starts_with('#') {
         let mut split = doc.splitn(2, '\n');
         let title = split
@@ -17,7 +172,15 @@ pub fn get_title_and_desc_from_doc(attrs: &[Attribute]) -> (Option<String>, Opti
         let maybe_desc = split.next().and_then(merge_description_lines);
         (none_if_empty(title), maybe_desc)
     } else {
-        (None, merge_description_lines(&doc))
+        (None, merge_description_lines(doc))
+    }
+}
+
+/// The doc comment as a single description, the title (if any) is kept as the first paragraph.
+pub fn get_description_from_doc(attrs: &[Attribute]) -> Option<String> {
+    match get_title_and_desc_from_doc(attrs) {
+        (Some(title), Some(desc)) => Some(format!("{title}\n\n{desc}")),
+        (title, desc) => title.or(desc),
     }
 }
 
@@ -99,4 +262,80 @@ mod tests {
         assert!(none_if_empty("   ".to_owned()).is_some());
         assert!(none_if_empty(" content ".to_owned()).is_some());
     }
+
+    fn route_docs(doc: &str) -> RouteDocs {
+        let item: syn::ItemFn = parse_str(&format!("#[doc = {doc:?}] fn f() {{}}")).unwrap();
+        get_route_docs(&item.attrs)
+    }
+
+    #[test]
+    fn test_get_route_docs_sections() {
+        let docs = route_docs(
+            "# Get a user\n\nReturns the user.\n\n# Parameters\n\n* `id` - The id of the\n  user.\n\
+            * name - The name.\n\n# Errors\n\n* 404 - The user does not exist.\n\
+            * other - Kept.\n\n# Responses\n\n- 200 - The user.\n\n# Deprecated\n\n\
+            Use `/v2/users` instead.\n\n## Notes\n\nAn extra section.",
+        );
+        assert_eq!(
+            docs,
+            RouteDocs {
+                title: Some("Get a user".to_owned()),
+                description: Some(
+                    "Returns the user.\n\n# Errors\n\n* other - Kept.\n\n## Notes\n\nAn extra section."
+                        .to_owned()
+                ),
+                parameters: vec![
+                    ("id".to_owned(), "The id of the user.".to_owned()),
+                    ("name".to_owned(), "The name.".to_owned()),
+                ],
+                responses: vec![
+                    (404, "The user does not exist.".to_owned()),
+                    (200, "The user.".to_owned()),
+                ],
+                deprecated: Some("Use `/v2/users` instead.".to_owned()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_get_route_docs_without_title() {
+        let docs = route_docs("# Deprecated\n\n# Errors\n\n* 500 - Oops.");
+        assert_eq!(docs.title, None);
+        assert_eq!(docs.description, None);
+        assert_eq!(docs.deprecated.as_deref(), Some(""));
+        assert_eq!(docs.responses, vec![(500, "Oops.".to_owned())]);
+    }
+
+    #[test]
+    fn test_get_route_docs_keeps_prose() {
+        let docs = route_docs(
+            "Lists the users.\n\n# Errors\n\nFails when the database is down.\n\n\
+            # Parameters\n\nAll parameters are optional.\n\n* `page` - The page.\n\
+            The first page is `0`.\n\nPages have 20 users.\n\n```\n# Responses\n```",
+        );
+        assert_eq!(docs.title, None);
+        assert_eq!(
+            docs.description.as_deref(),
+            Some(
+                "Lists the users.\n\n# Errors\n\nFails when the database is down.\n\n\
+                # Parameters\n\nAll parameters are optional.\n\nPages have 20 users.\n\n\
+                ``` # Responses ```"
+            )
+        );
+        assert_eq!(
+            docs.parameters,
+            vec![(
+                "page".to_owned(),
+                "The page. The first page is `0`.".to_owned()
+            )]
+        );
+        assert!(docs.responses.is_empty());
+        // Without a title, a kept section is not the title.
+        let docs = route_docs("# Errors\n\nFails when the database is down.");
+        assert_eq!(docs.title, None);
+        assert_eq!(
+            docs.description.as_deref(),
+            Some("# Errors\n\nFails when the database is down.")
+        );
+    }
 }
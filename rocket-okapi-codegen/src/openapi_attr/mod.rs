@@ -1,7 +1,8 @@
-mod doc_attr;
+mod catch_attr;
+pub(crate) mod doc_attr;
 mod route_attr;
 
-use crate::get_add_operation_fn_name;
+use crate::{get_add_catcher_fn_name, get_add_operation_fn_name};
 use darling::FromMeta;
 use proc_macro::TokenStream;
 use proc_macro2::Span;
@@ -10,6 +11,8 @@ use quote::ToTokens;
 use rocket_http::Method;
 use std::collections::BTreeMap as Map;
 use syn::ext::IdentExt;
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
 use syn::{
     parse_macro_input, FnArg, GenericArgument, Ident, ItemFn, PathArguments, PathSegment,
     ReturnType, Type, TypeTuple,
@@ -40,6 +43,56 @@ struct OpenApiAttribute {
 
     /// Mark this operation as deprecated in the documentation.
     pub deprecated: bool,
+
+    /// The type of the messages the server sends over a WebSocket or as Server-Sent Events.
+    /// When set, the route is also added to the AsyncAPI document.
+    pub send: Option<String>,
+
+    /// The type of the messages the server receives over a WebSocket.
+    /// When set, the route is also added to the AsyncAPI document.
+    pub receive: Option<String>,
+
+    /// The allowed `event` names of the Server-Sent Events. Only used together with `send`.
+    #[darling(multiple, rename = "event")]
+    pub events: Vec<String>,
+
+    /// Describes the meaning of the `id` field of the Server-Sent Events.
+    /// Only used together with `send`.
+    pub event_id: Option<String>,
+
+    /// An example of the request body: an expression of a type that implements `Serialize`.
+    #[darling(skip)]
+    pub request_example: Option<proc_macro2::TokenStream>,
+
+    /// Examples of the responses, by status code.
+    #[darling(skip)]
+    pub response_examples: Vec<ResponseExample>,
+
+    /// Documentation of parameters, by name.
+    #[darling(skip)]
+    pub params: Vec<ParamDoc>,
+}
+
+/// Documentation of a parameter:
+/// `param(name = "id", description = "...", example = 42, deprecated)`.
+#[derive(Debug, Default)]
+struct ParamDoc {
+    /// The name of the function argument or of the parameter, like a field of a query form or a
+    /// header of a request guard.
+    pub name: String,
+    pub description: Option<String>,
+    /// An expression of a type that implements `Serialize`.
+    pub example: Option<proc_macro2::TokenStream>,
+    pub deprecated: bool,
+}
+
+/// An example of a response: `response_example(status = 200, value = ...)`.
+#[derive(Debug)]
+struct ResponseExample {
+    /// The status code of the response.
+    pub status: u16,
+    /// An expression of a type that implements `Serialize`.
+    pub value: proc_macro2::TokenStream,
 }
 
 pub fn parse(args: TokenStream, input: TokenStream) -> TokenStream {
@@ -56,6 +109,13 @@ pub fn parse(args: TokenStream, input: TokenStream) -> TokenStream {
         }
     };
 
+    if let Some(status) = catch_attr::parse_catch_attr(&input.attrs) {
+        return match status {
+            Ok(status) => create_catcher_fn(input, status),
+            Err(e) => e.to_compile_error().into(),
+        };
+    }
+
     if entry_attributes.skip {
         return create_empty_route_operation_fn(input);
     }
@@ -71,8 +131,8 @@ fn parse_openapi_args_from_string(s: &str) -> Result<OpenApiAttribute, darling::
     if s.trim().is_empty() {
         return Ok(attr);
     }
-    for part in s.split(',') {
-        let part = part.trim();
+    for part in route_attr::parse_args_string_to_parts(s) {
+        let part = part.as_str();
         if part == "skip" {
             attr.skip = true;
             continue;
@@ -113,10 +173,136 @@ fn parse_openapi_args_from_string(s: &str) -> Result<OpenApiAttribute, darling::
             attr.ignore.push(val.to_string());
             continue;
         }
+        if let Some(rest) = part.strip_prefix("send =") {
+            let val = rest.trim().trim_matches(|c| c == '"' || c == '\'');
+            attr.send = Some(val.to_string());
+            continue;
+        }
+        if let Some(rest) = part.strip_prefix("receive =") {
+            let val = rest.trim().trim_matches(|c| c == '"' || c == '\'');
+            attr.receive = Some(val.to_string());
+            continue;
+        }
+        if let Some(rest) = part.strip_prefix("event =") {
+            let val = rest.trim().trim_matches(|c| c == '"' || c == '\'');
+            attr.events.push(val.to_string());
+            continue;
+        }
+        if let Some(rest) = part.strip_prefix("event_id =") {
+            let val = rest.trim().trim_matches(|c| c == '"' || c == '\'');
+            attr.event_id = Some(val.to_string());
+            continue;
+        }
+        if let Some(rest) = part.strip_prefix("request_example =") {
+            let value = syn::parse_str::<syn::Expr>(rest.trim()).map_err(|e| {
+                darling::Error::custom(format!("Invalid `request_example` value: {e}"))
+            })?;
+            attr.request_example = Some(value.into_token_stream());
+            continue;
+        }
+        if let Some(rest) = part
+            .strip_prefix("param")
+            .filter(|rest| rest.trim_start().starts_with('('))
+        {
+            attr.params.push(parse_param_doc(rest.trim())?);
+            continue;
+        }
+        if let Some(rest) = part.strip_prefix("response_example") {
+            attr.response_examples
+                .push(parse_response_example(rest.trim())?);
+            continue;
+        }
     }
     Ok(attr)
 }
 
+/// Parses the `(status = 200, value = ...)` part of a `response_example`.
+fn parse_response_example(s: &str) -> Result<ResponseExample, darling::Error> {
+    let error = |msg: &str| {
+        darling::Error::custom(format!(
+            "Invalid `response_example`, expected `response_example(status = 200, value = ...)`: {msg}"
+        ))
+    };
+    let args = s
+        .strip_prefix('(')
+        .and_then(|s| s.strip_suffix(')'))
+        .ok_or_else(|| error("missing parentheses"))?;
+    let parser = Punctuated::<syn::MetaNameValue, syn::Token![,]>::parse_terminated;
+    let args = parser.parse_str(args).map_err(|e| error(&e.to_string()))?;
+    let mut status = None;
+    let mut value = None;
+    for arg in args {
+        if arg.path.is_ident("status") {
+            let syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Int(lit),
+                ..
+            }) = &arg.value
+            else {
+                return Err(error("`status` must be an integer"));
+            };
+            status = Some(
+                lit.base10_parse::<u16>()
+                    .map_err(|e| error(&e.to_string()))?,
+            );
+        } else if arg.path.is_ident("value") {
+            value = Some(arg.value.into_token_stream());
+        } else {
+            return Err(error("unknown argument"));
+        }
+    }
+    Ok(ResponseExample {
+        status: status.ok_or_else(|| error("missing `status`"))?,
+        value: value.ok_or_else(|| error("missing `value`"))?,
+    })
+}
+
+/// Parses the `(name = "id", description = "...", example = 42, deprecated)` part of a `param`.
+fn parse_param_doc(s: &str) -> Result<ParamDoc, darling::Error> {
+    let error = |msg: &str| {
+        darling::Error::custom(format!(
+            "Invalid `param`, expected `param(name = \"id\", description = \"...\", \
+            example = ..., deprecated)`: {msg}"
+        ))
+    };
+    let args = s
+        .strip_prefix('(')
+        .and_then(|s| s.strip_suffix(')'))
+        .ok_or_else(|| error("missing parentheses"))?;
+    let parser = Punctuated::<syn::Meta, syn::Token![,]>::parse_terminated;
+    let args = parser.parse_str(args).map_err(|e| error(&e.to_string()))?;
+    let mut param = ParamDoc::default();
+    let mut name = None;
+    for arg in args {
+        match arg {
+            syn::Meta::Path(path) if path.is_ident("deprecated") => param.deprecated = true,
+            syn::Meta::NameValue(arg) if arg.path.is_ident("example") => {
+                param.example = Some(arg.value.into_token_stream());
+            }
+            syn::Meta::NameValue(arg) => {
+                let value = match &arg.value {
+                    syn::Expr::Lit(syn::ExprLit { lit, .. }) => lit,
+                    _ => return Err(error("expected a literal")),
+                };
+                match value {
+                    syn::Lit::Str(value) if arg.path.is_ident("name") => {
+                        name = Some(value.value());
+                    }
+                    syn::Lit::Str(value) if arg.path.is_ident("description") => {
+                        param.description = Some(value.value());
+                    }
+                    syn::Lit::Bool(value) if arg.path.is_ident("deprecated") => {
+                        param.deprecated = value.value;
+                    }
+                    _ => return Err(error("unknown or invalid argument")),
+                }
+            }
+            _ => return Err(error("unknown argument")),
+        }
+    }
+    param.name = name.ok_or_else(|| error("missing `name`"))?;
+    Ok(param)
+}
+
 fn create_empty_route_operation_fn(route_fn: ItemFn) -> TokenStream {
     let fn_name = get_add_operation_fn_name(&route_fn.sig.ident);
     TokenStream::from(quote! {
@@ -130,8 +316,46 @@ fn create_empty_route_operation_fn(route_fn: ItemFn) -> TokenStream {
     })
 }
 
+/// Create the function that adds the response of a catcher, for example `#[catch(404)]`.
+fn create_catcher_fn(catcher_fn: ItemFn, status: Option<u16>) -> TokenStream {
+    let fn_name = get_add_catcher_fn_name(&catcher_fn.sig.ident);
+    let name = catcher_fn.sig.ident.unraw().to_string();
+    let return_type = match catcher_fn.sig.output {
+        ReturnType::Type(_, ty) => type_replace_impl_trait(*ty),
+        ReturnType::Default => unit_type(),
+    };
+    let status = match status {
+        Some(status) => quote! { Some(#status) },
+        None => quote! { None },
+    };
+    let (title, desc) = doc_attr::get_title_and_desc_from_doc(&catcher_fn.attrs);
+    let description = match title
+        .into_iter()
+        .chain(desc)
+        .reduce(|t, d| format!("{t}\n\n{d}"))
+    {
+        Some(x) => quote!(Some(#x.to_owned())),
+        None => quote!(None),
+    };
+    TokenStream::from(quote! {
+        #[doc(hidden)]
+        pub fn #fn_name(
+            gen: &mut ::rocket_okapi::gen::OpenApiGenerator,
+        ) -> ::rocket_okapi::Result<()> {
+            let responses = <#return_type as ::rocket_okapi::response::OpenApiResponder>::responses(gen)?;
+            gen.add_catcher(::rocket_okapi::CatcherInfo {
+                name: #name.to_owned(),
+                module_path: module_path!().to_owned(),
+                status: #status,
+                description: #description,
+                responses,
+            })
+        }
+    })
+}
+
 /// Replace `EventStream<impl SOMETHING>`
-/// with `EventStream`
+/// with `EventStream`, and `JsonLines<impl Stream<Item = T>>` with `JsonLines<Empty<T>>`
 fn type_replace_impl_trait(ty: Type) -> Type {
     if let Type::Path(type_path) = &ty {
         if let Some(path_segment) = type_path.path.segments.first() {
@@ -154,8 +378,14 @@ fn _type_replace_impl_trait_generic_argument(
     gen_arg: &GenericArgument,
     path_segment: &PathSegment,
 ) -> Option<Type> {
-    if let GenericArgument::Type(Type::ImplTrait(_)) = gen_arg {
-        if path_segment.ident == "EventStream" {
+    if let GenericArgument::Type(Type::ImplTrait(impl_trait)) = gen_arg {
+        if path_segment.ident == "JsonLines" {
+            // The item type is documented, so keep it: `impl Stream<Item = T>` becomes `Empty<T>`
+            let item_type = impl_trait_stream_item(impl_trait)?;
+            return Some(Type::Verbatim(quote! {
+                JsonLines<rocket::futures::stream::Empty<#item_type>>
+            }));
+        } else if path_segment.ident == "EventStream" {
             // Return special type, the type of stream does not matter as long as something is present
             return Some(Type::Verbatim(quote! {
                 EventStream<rocket::futures::stream::Empty<rocket::response::stream::Event>>
@@ -180,11 +410,34 @@ fn _type_replace_impl_trait_generic_argument(
     None
 }
 
+/// Get `T` from `impl Stream<Item = T>`.
+fn impl_trait_stream_item(impl_trait: &syn::TypeImplTrait) -> Option<&Type> {
+    impl_trait.bounds.iter().find_map(|bound| {
+        let syn::TypeParamBound::Trait(trait_bound) = bound else {
+            return None;
+        };
+        let last_segment = trait_bound.path.segments.last()?;
+        let PathArguments::AngleBracketed(args) = &last_segment.arguments else {
+            return None;
+        };
+        args.args.iter().find_map(|arg| match arg {
+            GenericArgument::AssocType(assoc) if assoc.ident == "Item" => Some(&assoc.ty),
+            _ => None,
+        })
+    })
+}
+
 fn create_route_operation_fn(
     route_fn: ItemFn,
     route: route_attr::Route,
     entry_attributes: &OpenApiAttribute,
 ) -> TokenStream {
+    let docs = doc_attr::get_route_docs(&route_fn.attrs);
+    let param_docs = get_param_docs(
+        &route_fn.sig.inputs,
+        &docs.parameters,
+        &entry_attributes.params,
+    );
     let arg_types = get_arg_types(route_fn.sig.inputs.into_iter());
     let return_type = match route_fn.sig.output {
         ReturnType::Type(_, ty) => type_replace_impl_trait(*ty),
@@ -265,7 +518,7 @@ fn create_route_operation_fn(
         };
         params_names_used.push(arg.to_owned());
         params_nested_list.push(quote! {
-            <#ty as ::rocket_okapi::request::OpenApiFromForm>::form_multi_parameter(gen, #arg.to_owned(), true)?.into()
+            <#ty as ::rocket_okapi::request::OpenApiFromForm>::form_named_parameter(gen, #arg.to_owned(), true)?.into()
         })
     }
     // Multi Query parameters: `/?<param..>`
@@ -299,12 +552,79 @@ fn create_route_operation_fn(
             };
             // Add parameter to list
             params_names_used.push(data_param.clone());
-            quote! {
-                Some(<#ty as ::rocket_okapi::request::OpenApiFromData>::request_body(gen)?.into())
+            // Add the responses of the Data Guard, for when the body could not be parsed.
+            request_guard_responses.push(quote! {
+                <#ty as ::rocket_okapi::request::OpenApiFromData>::get_responses(gen)?
+            });
+            // An example from the attribute, or else from the `OpenApiExample` trait.
+            let example = match &entry_attributes.request_example {
+                Some(value) => quote! { Some(::rocket_okapi::example::to_example(&(#value))?) },
+                None => probe_example(ty),
+            };
+            quote! {{
+                let mut request_body = <#ty as ::rocket_okapi::request::OpenApiFromData>::request_body(gen)?;
+                if let Some(example) = #example {
+                    ::rocket_okapi::util::set_request_body_example(&mut request_body, example);
+                }
+                Some(request_body.into())
+            }}
+        }
+        None if entry_attributes.request_example.is_some() => {
+            return quote! {
+                compile_error!("`request_example` can only be used on routes with a `data` parameter.");
             }
+            .into();
+        }
+        None => quote! { None },
+    };
+
+    // -- Format --
+    // https://rocket.rs/v0.5/guide/requests/#format
+    let format = match &route.media_type {
+        Some(media_type) => {
+            let media_type = media_type.to_string();
+            quote! { Some(#media_type.to_owned()) }
         }
         None => quote! { None },
     };
+    // For methods with a payload the format is matched against the `Content-Type` of the request,
+    // for all other methods against the `Accept` header, so it describes the response.
+    let (request_body, set_format) = match &route.media_type {
+        Some(media_type) if route.method.supports_payload() => {
+            let media_type = media_type.to_string();
+            (
+                quote! {{
+                    let mut request_body = #request_body;
+                    if let Some(request_body) = request_body.as_mut() {
+                        ::rocket_okapi::util::set_request_body_content_type(request_body, #media_type)?;
+                    }
+                    request_body
+                }},
+                quote! {},
+            )
+        }
+        Some(media_type) => {
+            let media_type = media_type.to_string();
+            (
+                request_body,
+                quote! {
+                    ::rocket_okapi::util::set_success_content_type(&mut responses, #media_type)?;
+                },
+            )
+        }
+        None => match file_name_content_type(&route) {
+            // The content type of a `NamedFile` is known when the path ends with its file name.
+            Some(content_type) => (
+                request_body,
+                quote! {
+                    if <#return_type as ::rocket_okapi::response::OpenApiResponderInner>::sends_named_file() {
+                        ::rocket_okapi::util::set_success_content_type(&mut responses, #content_type)?;
+                    }
+                },
+            ),
+            None => (request_body, quote! {}),
+        },
+    };
 
     // -- Request Guards --
     // https://rocket.rs/v0.5/guide/requests/#request-guards
@@ -318,7 +638,7 @@ fn create_route_operation_fn(
         if !params_names_used.contains(arg) {
             params_names_used.push(arg.to_owned());
             params_request_guards.push(quote! {
-                <#ty as ::rocket_okapi::request::OpenApiFromRequest>::from_request_input(gen, #arg.to_owned(), true)?.into()
+                (#arg, <#ty as ::rocket_okapi::request::OpenApiFromRequest>::from_request_input(gen, #arg.to_owned(), true)?.into())
             });
             request_guard_responses.push(quote! {
                 <#ty as ::rocket_okapi::request::OpenApiFromRequest>::get_responses(gen)?.into()
@@ -340,6 +660,49 @@ fn create_route_operation_fn(
         }
     }
 
+    // Check if every `param(name = ...)` can match a parameter. Names that are not arguments can
+    // only be checked when the specification is generated, because query parameters and request
+    // guards can add parameters with other names, like the fields of a query form.
+    let unmatched_params: Vec<&str> = entry_attributes
+        .params
+        .iter()
+        .map(|param| param.name.as_str())
+        .filter(|name| !arg_types.contains_key(*name))
+        .collect();
+    let can_add_named_params = route.query_params().next().is_some()
+        || route.query_multi_params().next().is_some()
+        || !params_request_guards.is_empty();
+    if let Some(name) = unmatched_params.first() {
+        if !can_add_named_params {
+            return TokenStream::from(quote! {
+                compile_error!(concat!(
+                    "The `param(name = \"",
+                    #name,
+                    "\")` does not match any argument or parameter of the route."
+                ));
+            });
+        }
+    }
+    let check_unmatched_params = if unmatched_params.is_empty() {
+        quote! {}
+    } else {
+        let route_name = route_fn.sig.ident.unraw().to_string();
+        quote! {
+            for name in [#(#unmatched_params),*] {
+                let found = parameters.iter().any(|parameter| match parameter {
+                    ::rocket_okapi::okapi::openapi3::RefOr::Object(parameter) => parameter.name == name,
+                    ::rocket_okapi::okapi::openapi3::RefOr::Ref(_) => false,
+                });
+                if !found {
+                    return Err(::rocket_okapi::OpenApiError::new(format!(
+                        "The `param(name = \"{name}\")` of `{}` does not match any argument or parameter of the route.",
+                        #route_name,
+                    )));
+                }
+            }
+        }
+    };
+
     let fn_name = get_add_operation_fn_name(&route_fn.sig.ident);
     let path = route
         .origin
@@ -349,11 +712,16 @@ fn create_route_operation_fn(
         .replace("..>", "}")
         .replace('>', "}");
     let method = Ident::new(&to_pascal_case_string(route.method), Span::call_site());
-    let (title, desc) = doc_attr::get_title_and_desc_from_doc(&route_fn.attrs);
-    let title = match title {
+    let title = match docs.title {
         Some(x) => quote!(Some(#x.to_owned())),
         None => quote!(None),
     };
+    // The note of the `# Deprecated` section is kept as the last paragraph of the description.
+    let desc = match (docs.description, docs.deprecated.as_deref()) {
+        (desc, None | Some("")) => desc,
+        (Some(desc), Some(note)) => Some(format!("{desc}\n\n**Deprecated:** {note}")),
+        (None, Some(note)) => Some(format!("**Deprecated:** {note}")),
+    };
     let desc = match desc {
         Some(x) => quote!(Some(#x.to_owned())),
         None => quote!(None),
@@ -365,13 +733,176 @@ fn create_route_operation_fn(
         .map(|tag| quote!(#tag.to_owned()))
         .collect::<Vec<_>>();
 
-    let deprecated = entry_attributes.deprecated;
+    let deprecated = entry_attributes.deprecated || docs.deprecated.is_some();
 
     // In case the user has set a manual `operation_id` use that, otherwise use generated one.
     let operation_id = match &entry_attributes.operation_id {
         Some(operation_id) => quote! { #operation_id.into() },
-        // Use value set by the variable set from the function below.
-        None => quote! { operation_id },
+        // Use the strategy from the settings, based on the variable set from the function below.
+        None => {
+            let function_name = route_fn.sig.ident.unraw().to_string();
+            quote! {
+                gen.operation_id(&::rocket_okapi::settings::OperationIdInput {
+                    function_name: #function_name,
+                    qualified_name: &operation_id,
+                    method: ::rocket::http::Method::#method,
+                    path: #path,
+                })
+            }
+        }
+    };
+
+    // -- AsyncAPI channel --
+    // Routes that declare the messages they send or receive are also documented as a channel.
+    let add_channel = if entry_attributes.send.is_some() || entry_attributes.receive.is_some() {
+        let mut message_schemas = Vec::new();
+        for message_type in [&entry_attributes.send, &entry_attributes.receive] {
+            message_schemas.push(match message_type {
+                Some(message_type) => match syn::parse_str::<Type>(message_type) {
+                    Ok(ty) => quote! { Some(gen.json_schema::<#ty>()) },
+                    Err(_) => {
+                        return TokenStream::from(quote! {
+                            compile_error!(concat!("Could not parse message type `", #message_type, "`."));
+                        })
+                    }
+                },
+                None => quote! { None },
+            });
+        }
+        let (send, receive) = (&message_schemas[0], &message_schemas[1]);
+        let events = &entry_attributes.events;
+        let event_id = match &entry_attributes.event_id {
+            Some(event_id) => quote! { Some(#event_id) },
+            None => quote! { None },
+        };
+        quote! {
+            let send: Option<::rocket_okapi::okapi::openapi3::SchemaObject> = #send;
+            let receive: Option<::rocket_okapi::okapi::openapi3::SchemaObject> = #receive;
+            // Routes that respond with a stream of Server-Sent Events, like `EventStream`, are
+            // documented as such by their `OpenApiResponder`. Everything else is a WebSocket.
+            let protocol = if ::rocket_okapi::util::has_event_stream_response(&responses) {
+                ::rocket_okapi::ChannelProtocol::EventStream
+            } else {
+                ::rocket_okapi::ChannelProtocol::WebSocket
+            };
+            // Document the shape of every event in the `text/event-stream` response.
+            if protocol == ::rocket_okapi::ChannelProtocol::EventStream {
+                if let Some(data_schema) = &send {
+                    ::rocket_okapi::util::set_event_stream_item_schema(
+                        &mut responses,
+                        data_schema.clone(),
+                        &[#(#events),*],
+                        #event_id,
+                    )?;
+                }
+            }
+            gen.add_channel(::rocket_okapi::ChannelInfo {
+                path: #path.to_owned(),
+                protocol,
+                operation_id: Some(operation_id.clone()),
+                summary: #title,
+                description: #desc,
+                parameters: parameters.clone(),
+                send,
+                receive,
+            })?;
+        }
+    } else {
+        quote! {}
+    };
+
+    // -- Parameter documentation --
+    // The docs are matched with the name of the argument that adds a parameter, or with the name
+    // of the parameter itself, like the fields of a query form.
+    let (document_parameter, document_guard_parameter, document_parameters) = if param_docs
+        .is_empty()
+    {
+        (quote! {}, quote! {}, quote! {})
+    } else {
+        let arms = param_docs.iter().map(|(name, doc)| {
+            let description = match &doc.description {
+                Some(description) => quote! { Some(#description) },
+                None => quote! { None },
+            };
+            let example = match &doc.example {
+                Some(example) => {
+                    quote! { Some(::rocket_okapi::example::to_example(&(#example))?) }
+                }
+                None => quote! { None },
+            };
+            let deprecated = doc.deprecated;
+            quote! {
+                #name => ::rocket_okapi::util::set_parameter_docs(
+                    parameter,
+                    #description,
+                    #example,
+                    #deprecated,
+                ),
+            }
+        });
+        (
+            quote! {
+                let document_parameter = |
+                    parameter: &mut ::rocket_okapi::okapi::openapi3::Parameter,
+                    name: &str,
+                | -> ::rocket_okapi::Result<()> {
+                    match name {
+                        #(#arms)*
+                        _ => {}
+                    }
+                    Ok(())
+                };
+            },
+            quote! {
+                document_parameter(&mut p, name)?;
+            },
+            quote! {
+                for parameter in &mut parameters {
+                    if let ::rocket_okapi::okapi::openapi3::RefOr::Object(parameter) = parameter {
+                        let name = parameter.name.clone();
+                        document_parameter(parameter, &name)?;
+                    }
+                }
+            },
+        )
+    };
+
+    let (guard_name, guard_parameter) = if param_docs.is_empty() {
+        (quote! { _ }, quote! { p })
+    } else {
+        (quote! { name }, quote! { mut p })
+    };
+
+    // -- Response descriptions --
+    // The descriptions of the `# Errors` and `# Responses` sections of the doc comment.
+    let set_response_descriptions = docs.responses.iter().map(|(status, description)| {
+        quote! {
+            ::rocket_okapi::util::set_response_description(&mut responses, #status, #description)?;
+        }
+    });
+
+    // -- Examples --
+    // The example of the successful responses from the `OpenApiExample` trait, then the examples
+    // from the attribute, which take precedence.
+    let success_example = probe_example(&return_type);
+    let response_examples =
+        entry_attributes
+            .response_examples
+            .iter()
+            .map(|ResponseExample { status, value }| {
+                quote! {
+                    ::rocket_okapi::util::set_response_example(
+                        &mut responses,
+                        #status,
+                        ::rocket_okapi::example::to_example(&(#value))?,
+                    )?;
+                }
+            });
+    let set_response_examples = quote! {
+        if let Some(example) = #success_example {
+            ::rocket_okapi::util::set_success_example(&mut responses, example);
+        }
+        #(#response_examples)*
     };
 
     // eprintln!(
@@ -385,11 +916,16 @@ fn create_route_operation_fn(
             operation_id: String,
         ) -> ::rocket_okapi::Result<()> {
             let mut responses = <#return_type as ::rocket_okapi::response::OpenApiResponder>::responses(gen)?;
-            // Add responses from Request Guards.
+            gen.resolve_response_refs(&mut responses);
+            #set_format
+            // Add responses from Request and Data Guards.
             let request_guard_responses = vec![#(#request_guard_responses),*];
             for request_guard_response in request_guard_responses {
                 ::rocket_okapi::okapi::merge::merge_responses(&mut responses, &request_guard_response)?;
             }
+            gen.resolve_response_refs(&mut responses);
+            #(#set_response_descriptions)*
+            #set_response_examples
 
             let request_body = #request_body;
             // Add the security scheme that are quired for all the routes.
@@ -409,14 +945,17 @@ fn create_route_operation_fn(
             }
             // Body Data does not add any parameters
 
+            #document_parameter
+
             // Add all Request Guards
-            let request_guards_route: Vec<::rocket_okapi::request::RequestHeaderInput> = vec![#(#params_request_guards),*];
-            for request_guard_route in request_guards_route {
+            let request_guards_route: Vec<(&str, ::rocket_okapi::request::RequestHeaderInput)> = vec![#(#params_request_guards),*];
+            for (#guard_name, request_guard_route) in request_guards_route {
                 use ::rocket_okapi::request::RequestHeaderInput;
                 match request_guard_route {
                     // Add Parameters
-                    RequestHeaderInput::Parameter(p) => {
-                       parameters.push(p.into());
+                    RequestHeaderInput::Parameter(#guard_parameter) => {
+                        #document_guard_parameter
+                        parameters.push(p.into());
                     }
                     // Add Security Schemes, different section.
                     RequestHeaderInput::Security(name, schema, requirement) => {
@@ -439,6 +978,9 @@ fn create_route_operation_fn(
                 }
             }
 
+            #document_parameters
+            #check_unmatched_params
+
             // Add `security` section if list is not empty
             let security = if security_requirements.is_empty() {
                 None
@@ -451,12 +993,15 @@ fn create_route_operation_fn(
             } else {
                 Some(server_requirements)
             };
+            let operation_id: String = #operation_id;
+            // Add route/endpoint to AsyncApi object, if it sends or receives messages.
+            #add_channel
             // Add route/endpoint to OpenApi object.
             gen.add_operation(::rocket_okapi::OperationInfo {
                 path: #path.to_owned(),
                 method: ::rocket::http::Method::#method,
                 operation: ::rocket_okapi::okapi::openapi3::Operation {
-                    operation_id: Some(#operation_id),
+                    operation_id: Some(operation_id),
                     responses,
                     request_body,
                     parameters,
@@ -468,8 +1013,8 @@ fn create_route_operation_fn(
                     deprecated: #deprecated,
                     ..Default::default()
                 },
-            });
-            Ok(())
+                format: #format,
+            })
         }
     });
     // eprintln!("create function: {}", generated_function.to_string());
@@ -490,6 +1035,87 @@ fn to_pascal_case_string(method: Method) -> String {
     format!("{first_char}{rest}")
 }
 
+/// The example of `ty` if it implements `OpenApiExample`, as an `Option<Value>` expression.
+fn probe_example(ty: &Type) -> proc_macro2::TokenStream {
+    quote! {{
+        #[allow(unused_imports)]
+        use ::rocket_okapi::example::{ViaNoExample as _, ViaOpenApiExample as _};
+        (&&::rocket_okapi::example::ExampleProbe::<#ty>::new()).probe_example()
+    }}
+}
+
+/// The documentation of the parameters, by name: the `# Parameters` section of the doc comment
+/// of the route, overridden by the doc comments of the function arguments and then by the
+/// `param(...)`s of the attribute.
+fn get_param_docs<'a>(
+    args: impl IntoIterator<Item = &'a FnArg>,
+    section: &[(String, String)],
+    params: &[ParamDoc],
+) -> Map<String, ParamDoc> {
+    let mut result = Map::new();
+    for (name, description) in section {
+        result.insert(
+            name.clone(),
+            ParamDoc {
+                name: name.clone(),
+                description: Some(description.clone()),
+                ..ParamDoc::default()
+            },
+        );
+    }
+    for arg in args {
+        if let syn::FnArg::Typed(arg) = arg {
+            if let syn::Pat::Ident(ident) = &*arg.pat {
+                if let Some(description) = doc_attr::get_description_from_doc(&arg.attrs) {
+                    let name = ident.ident.unraw().to_string();
+                    result.insert(
+                        name.clone(),
+                        ParamDoc {
+                            name,
+                            description: Some(description),
+                            ..ParamDoc::default()
+                        },
+                    );
+                }
+            }
+        }
+    }
+    for param in params {
+        let doc = result.entry(param.name.clone()).or_default();
+        doc.name.clone_from(&param.name);
+        if param.description.is_some() {
+            doc.description.clone_from(&param.description);
+        }
+        if param.example.is_some() {
+            doc.example.clone_from(&param.example);
+        }
+        doc.deprecated |= param.deprecated;
+    }
+    result
+}
+
+/// Removes the doc comments of the function arguments, which are only allowed on items.
+/// They are used as the descriptions of the parameters.
+pub fn strip_arg_docs(input: TokenStream) -> TokenStream {
+    let mut route_fn = match syn::parse::<ItemFn>(input.clone()) {
+        Ok(route_fn) => route_fn,
+        Err(_) => return input,
+    };
+    let mut changed = false;
+    for arg in &mut route_fn.sig.inputs {
+        if let syn::FnArg::Typed(arg) = arg {
+            let len = arg.attrs.len();
+            arg.attrs.retain(|attr| !attr.path().is_ident("doc"));
+            changed |= arg.attrs.len() != len;
+        }
+    }
+    if changed {
+        route_fn.into_token_stream().into()
+    } else {
+        input
+    }
+}
+
 fn get_arg_types(args: impl Iterator<Item = FnArg>) -> Map<String, Type> {
     let mut result = Map::new();
     for arg in args {
@@ -507,6 +1133,17 @@ fn get_arg_types(args: impl Iterator<Item = FnArg>) -> Map<String, Type> {
     result
 }
 
+/// The content type of the file named by the route path, if it ends with a static file name with
+/// a known extension, like `/favicon.ico`. Rocket sets it as the content type of a `NamedFile`.
+fn file_name_content_type(route: &route_attr::Route) -> Option<String> {
+    let last_segment = route.origin.path().segments().last()?;
+    if last_segment.starts_with('<') {
+        return None;
+    }
+    let (_, extension) = last_segment.rsplit_once('.')?;
+    rocket_http::ContentType::from_extension(extension).map(|content_type| content_type.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -543,4 +1180,68 @@ mod tests {
         assert_eq!(attr.tags[0], "Users");
         assert_eq!(attr.operation_id.as_deref(), Some("explicitId"));
     }
+
+    #[test]
+    fn parse_examples() {
+        let attr = parse_openapi_args_from_string(
+            "request_example = json!({ \"a\": [1, 2] }), \
+            response_example(status = 201, value = \"created\"), tag = \"Users\"",
+        )
+        .unwrap();
+        assert!(attr.request_example.is_some());
+        assert_eq!(attr.response_examples.len(), 1);
+        assert_eq!(attr.response_examples[0].status, 201);
+        assert_eq!(attr.response_examples[0].value.to_string(), "\"created\"");
+        assert_eq!(attr.tags, vec!["Users"]);
+    }
+
+    #[test]
+    fn parse_invalid_response_example() {
+        assert!(parse_openapi_args_from_string("response_example(value = 1)").is_err());
+        assert!(
+            parse_openapi_args_from_string("response_example(status = \"200\", value = 1)")
+                .is_err()
+        );
+        assert!(parse_openapi_args_from_string("response_example(status = 200)").is_err());
+    }
+
+    #[test]
+    fn parse_params() {
+        let attr = parse_openapi_args_from_string(
+            "param(name = \"id\", description = \"The id.\", example = 42, deprecated), \
+            param(name = \"q\", deprecated = false)",
+        )
+        .unwrap();
+        assert_eq!(attr.params.len(), 2);
+        assert_eq!(attr.params[0].name, "id");
+        assert_eq!(attr.params[0].description.as_deref(), Some("The id."));
+        assert_eq!(attr.params[0].example.as_ref().unwrap().to_string(), "42");
+        assert!(attr.params[0].deprecated);
+        assert_eq!(attr.params[1].name, "q");
+        assert!(!attr.params[1].deprecated);
+    }
+
+    #[test]
+    fn parse_invalid_params() {
+        assert!(parse_openapi_args_from_string("param(description = \"The id.\")").is_err());
+        assert!(parse_openapi_args_from_string("param(name = id)").is_err());
+        assert!(parse_openapi_args_from_string("param(name = \"id\", title = \"Id\")").is_err());
+    }
+
+    #[test]
+    fn param_docs_from_arg_doc_comments() {
+        let route_fn: ItemFn = syn::parse_str(
+            "fn f(/// The id.\n id: u64, /// The query.\n q: String, key: ApiKey) {}",
+        )
+        .unwrap();
+        let params = vec![ParamDoc {
+            name: "q".to_owned(),
+            description: Some("Overridden.".to_owned()),
+            ..ParamDoc::default()
+        }];
+        let docs = get_param_docs(&route_fn.sig.inputs, &[], &params);
+        assert_eq!(docs.len(), 2);
+        assert_eq!(docs["id"].description.as_deref(), Some("The id."));
+        assert_eq!(docs["q"].description.as_deref(), Some("Overridden."));
+    }
 }
@@ -12,7 +12,6 @@ use syn::Attribute;
 pub struct Route {
     pub method: Method,
     pub origin: Origin<'static>,
-    #[allow(dead_code)]
     pub media_type: Option<MediaType>,
     pub data_param: Option<String>,
 }
@@ -239,12 +238,13 @@ fn extract_inner_args_string(attr: &Attribute) -> Option<String> {
     None
 }
 
-fn parse_args_string_to_parts(s: &str) -> Vec<String> {
-    // Split on commas at top-level, respecting strings inside quotes
+pub(crate) fn parse_args_string_to_parts(s: &str) -> Vec<String> {
+    // Split on commas at top-level, respecting strings inside quotes and brackets
     let mut parts = Vec::new();
     let mut current = String::new();
     let mut in_quotes = false;
     let mut escape = false;
+    let mut depth = 0usize;
     for c in s.chars() {
         if escape {
             current.push(c);
@@ -261,7 +261,14 @@ fn parse_args_string_to_parts(s: &str) -> Vec<String> {
             current.push(c);
             continue;
         }
-        if c == ',' && !in_quotes {
+        if !in_quotes {
+            match c {
+                '(' | '[' | '{' => depth += 1,
+                ')' | ']' | '}' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+        }
+        if c == ',' && !in_quotes && depth == 0 {
             parts.push(current.trim().to_string());
             current.clear();
         } else {
@@ -390,6 +397,21 @@ mod tests {
         assert!(parts.iter().any(|p| p.contains("data")));
     }
 
+    #[test]
+    fn test_parse_args_string_to_parts_brackets() {
+        let s =
+            "tag = \"a,b\", response_example(status = 200, value = json!({ \"a\": [1, 2] })), skip";
+        let parts = parse_args_string_to_parts(s);
+        assert_eq!(
+            parts,
+            vec![
+                "tag = \"a,b\"",
+                "response_example(status = 200, value = json!({ \"a\": [1, 2] }))",
+                "skip",
+            ]
+        );
+    }
+
     #[test]
     fn test_extract_inner_args_string() {
         let item: syn::ItemFn = parse_str("#[get(\"/a\")] fn f() {} ").unwrap();
@@ -1,4 +1,5 @@
 use crate::get_add_operation_fn_name;
+use crate::package_metadata::create_set_package_metadata;
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
@@ -8,34 +9,16 @@ use syn::{parse::Parser, punctuated::Punctuated, token::Comma, Path, Result};
 pub fn create_openapi_spec(routes: TokenStream) -> Result<TokenStream2> {
     let paths = <Punctuated<Path, Comma>>::parse_terminated.parse(routes)?;
     let add_operations = create_add_operations(paths);
+    let set_package_metadata = create_set_package_metadata()?;
     Ok(quote! {
         |settings: &::rocket_okapi::settings::OpenApiSettings| -> ::rocket_okapi::okapi::openapi3::OpenApi {
             let mut gen = ::rocket_okapi::gen::OpenApiGenerator::new(settings);
             #add_operations
-            let mut spec = gen.into_openapi();
-            let mut info = ::rocket_okapi::okapi::openapi3::Info {
-                title: env!("CARGO_PKG_NAME").to_owned(),
-                version: env!("CARGO_PKG_VERSION").to_owned(),
-                ..Default::default()
-            };
-            if !env!("CARGO_PKG_DESCRIPTION").is_empty() {
-                info.description = Some(env!("CARGO_PKG_DESCRIPTION").to_owned());
+            for add_catcher in &settings.catchers {
+                add_catcher(&mut gen).expect("Could not generate OpenAPI response for catcher.");
             }
-            if !env!("CARGO_PKG_REPOSITORY").is_empty() {
-                info.contact = Some(::rocket_okapi::okapi::openapi3::Contact{
-                    name: Some("Repository".to_owned()),
-                    url: Some(env!("CARGO_PKG_REPOSITORY").to_owned()),
-                    ..Default::default()
-                });
-            }
-            if !env!("CARGO_PKG_HOMEPAGE").is_empty() {
-                info.contact = Some(::rocket_okapi::okapi::openapi3::Contact{
-                    name: Some("Homepage".to_owned()),
-                    url: Some(env!("CARGO_PKG_HOMEPAGE").to_owned()),
-                    ..Default::default()
-                });
-            }
-            spec.info = info;
+            #set_package_metadata
+            let spec = gen.into_openapi();
 
             spec
         }
@@ -46,34 +29,55 @@ pub fn create_openapi_spec(routes: TokenStream) -> Result<TokenStream2> {
 pub(crate) fn create_openapi_spec_ts(routes: TokenStream2) -> Result<TokenStream2> {
     let paths = <Punctuated<Path, Comma>>::parse_terminated.parse2(routes)?;
     let add_operations = create_add_operations(paths);
+    let set_package_metadata = create_set_package_metadata()?;
     Ok(quote! {
         |settings: &::rocket_okapi::settings::OpenApiSettings| -> ::rocket_okapi::okapi::openapi3::OpenApi {
             let mut gen = ::rocket_okapi::gen::OpenApiGenerator::new(settings);
             #add_operations
-            let mut spec = gen.into_openapi();
-            let mut info = ::rocket_okapi::okapi::openapi3::Info {
-                title: env!("CARGO_PKG_NAME").to_owned(),
-                version: env!("CARGO_PKG_VERSION").to_owned(),
-                ..Default::default()
-            };
-            if !env!("CARGO_PKG_DESCRIPTION").is_empty() {
-                info.description = Some(env!("CARGO_PKG_DESCRIPTION").to_owned());
-            }
-            if !env!("CARGO_PKG_REPOSITORY").is_empty() {
-                info.contact = Some(::rocket_okapi::okapi::openapi3::Contact{
-                    name: Some("Repository".to_owned()),
-                    url: Some(env!("CARGO_PKG_REPOSITORY").to_owned()),
-                    ..Default::default()
-                });
+            for add_catcher in &settings.catchers {
+                add_catcher(&mut gen).expect("Could not generate OpenAPI response for catcher.");
             }
-            if !env!("CARGO_PKG_HOMEPAGE").is_empty() {
-                info.contact = Some(::rocket_okapi::okapi::openapi3::Contact{
-                    name: Some("Homepage".to_owned()),
-                    url: Some(env!("CARGO_PKG_HOMEPAGE").to_owned()),
-                    ..Default::default()
-                });
+            #set_package_metadata
+            let spec = gen.into_openapi();
+
+            spec
+        }
+    })
+}
+
+/// Parses routes and returns a function that takes `OpenApiSettings` and returns the `OpenApi`
+/// spec and the `AsyncApi` spec, if any route sends or receives messages.
+pub fn create_openapi_and_asyncapi_spec(routes: TokenStream) -> Result<TokenStream2> {
+    let paths = <Punctuated<Path, Comma>>::parse_terminated.parse(routes)?;
+    let add_operations = create_add_operations(paths);
+    let set_package_metadata = create_set_package_metadata()?;
+    Ok(quote! {
+        |settings: &::rocket_okapi::settings::OpenApiSettings| -> (
+            ::rocket_okapi::okapi::openapi3::OpenApi,
+            Option<::rocket_okapi::okapi::asyncapi::AsyncApi>,
+        ) {
+            let mut gen = ::rocket_okapi::gen::OpenApiGenerator::new(settings);
+            #add_operations
+            for add_catcher in &settings.catchers {
+                add_catcher(&mut gen).expect("Could not generate OpenAPI response for catcher.");
             }
-            spec.info = info;
+            #set_package_metadata
+            gen.into_openapi_and_asyncapi()
+        }
+    })
+}
+
+/// Parses routes and returns a function that takes `OpenApiSettings` and returns `AsyncApi` spec.
+pub fn create_asyncapi_spec(routes: TokenStream) -> Result<TokenStream2> {
+    let paths = <Punctuated<Path, Comma>>::parse_terminated.parse(routes)?;
+    let add_operations = create_add_operations(paths);
+    let set_package_metadata = create_set_package_metadata()?;
+    Ok(quote! {
+        |settings: &::rocket_okapi::settings::OpenApiSettings| -> ::rocket_okapi::okapi::asyncapi::AsyncApi {
+            let mut gen = ::rocket_okapi::gen::OpenApiGenerator::new(settings);
+            #add_operations
+            #set_package_metadata
+            let spec = gen.into_asyncapi();
 
             spec
         }
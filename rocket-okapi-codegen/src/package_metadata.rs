@@ -0,0 +1,341 @@
+//! Maps the package information from the `Cargo.toml` of the crate that uses the macros to the
+//! `Info`, `externalDocs` and `servers` of the generated specification.
+//!
+//! Besides the standard fields (`CARGO_PKG_*`) a `[package.metadata.okapi]` table is read:
+//! ```toml
+//! [package.metadata.okapi]
+//! terms_of_service = "https://example.com/terms"
+//! logo = { url = "https://example.com/logo.png", alt_text = "Example", background_color = "#FFFFFF", href = "https://example.com" }
+//! servers = [{ url = "https://api.example.com", description = "Production" }]
+//! ```
+
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{Error, Result};
+
+#[derive(Debug, Default, PartialEq)]
+struct OkapiMetadata {
+    terms_of_service: Option<String>,
+    logo: Option<Logo>,
+    servers: Vec<ServerMetadata>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct Logo {
+    url: String,
+    alt_text: Option<String>,
+    background_color: Option<String>,
+    href: Option<String>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct ServerMetadata {
+    url: String,
+    description: Option<String>,
+}
+
+/// Create the code that sets the package information on the `OpenApiGenerator` called `gen`.
+pub(crate) fn create_set_package_metadata() -> Result<TokenStream2> {
+    let okapi_metadata = match std::env::var("CARGO_MANIFEST_DIR") {
+        Ok(dir) => match std::fs::read_to_string(std::path::Path::new(&dir).join("Cargo.toml")) {
+            Ok(manifest) => parse_okapi_metadata(&manifest)?,
+            Err(_) => OkapiMetadata::default(),
+        },
+        Err(_) => OkapiMetadata::default(),
+    };
+    let info = create_info(&okapi_metadata);
+    let external_docs = match env_var("CARGO_PKG_HOMEPAGE") {
+        Some(homepage) => quote! {
+            Some(::rocket_okapi::okapi::openapi3::ExternalDocs {
+                url: #homepage.to_owned(),
+                ..Default::default()
+            })
+        },
+        None => quote! { None },
+    };
+    let servers = okapi_metadata.servers.iter().map(|server| {
+        let url = &server.url;
+        let description = opt_string(&server.description);
+        quote! {
+            ::rocket_okapi::okapi::openapi3::Server {
+                url: #url.to_owned(),
+                description: #description,
+                ..Default::default()
+            }
+        }
+    });
+    Ok(quote! {
+        // Rebuild when the `[package.metadata.okapi]` table changes.
+        const _: &[u8] = include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml"));
+        gen.set_info(#info);
+        gen.set_external_docs(#external_docs);
+        gen.set_servers(vec![#(#servers),*]);
+    })
+}
+
+/// Create the `Info` object from the package information of the crate that uses the macro.
+fn create_info(okapi_metadata: &OkapiMetadata) -> TokenStream2 {
+    let title = env_var("CARGO_PKG_NAME").unwrap_or_default();
+    let version = env_var("CARGO_PKG_VERSION").unwrap_or_default();
+    let description = opt_string(&env_var("CARGO_PKG_DESCRIPTION"));
+    let terms_of_service = opt_string(&okapi_metadata.terms_of_service);
+    let contact = match env_var("CARGO_PKG_AUTHORS").map(|authors| parse_authors(&authors)) {
+        Some((name, email)) => {
+            let name = opt_string(&name);
+            let email = opt_string(&email);
+            quote! {
+                Some(::rocket_okapi::okapi::openapi3::Contact {
+                    name: #name,
+                    email: #email,
+                    ..Default::default()
+                })
+            }
+        }
+        None => quote! { None },
+    };
+    let license = match env_var("CARGO_PKG_LICENSE") {
+        Some(license) => {
+            let url = opt_string(&license_url(&license));
+            quote! {
+                Some(::rocket_okapi::okapi::openapi3::License {
+                    name: #license.to_owned(),
+                    url: #url,
+                    ..Default::default()
+                })
+            }
+        }
+        None => quote! { None },
+    };
+    let mut extensions = Vec::new();
+    if let Some(repository) = env_var("CARGO_PKG_REPOSITORY") {
+        extensions.push(quote! {
+            info.extensions.insert("x-repository".to_owned(), #repository.into());
+        });
+    }
+    if let Some(rust_version) = env_var("CARGO_PKG_RUST_VERSION") {
+        extensions.push(quote! {
+            info.extensions.insert("x-rust-version".to_owned(), #rust_version.into());
+        });
+    }
+    if let Some(logo) = &okapi_metadata.logo {
+        let url = &logo.url;
+        let fields = [
+            ("altText", &logo.alt_text),
+            ("backgroundColor", &logo.background_color),
+            ("href", &logo.href),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| value.as_ref().map(|value| (key, value)))
+        .map(|(key, value)| {
+            quote! { logo.insert(#key.to_owned(), #value.into()); }
+        });
+        extensions.push(quote! {
+            let mut logo = ::rocket::serde::json::serde_json::Map::new();
+            logo.insert("url".to_owned(), #url.into());
+            #(#fields)*
+            info.extensions.insert("x-logo".to_owned(), logo.into());
+        });
+    }
+    quote! {{
+        #[allow(unused_mut)]
+        let mut info = ::rocket_okapi::okapi::openapi3::Info {
+            title: #title.to_owned(),
+            version: #version.to_owned(),
+            description: #description,
+            terms_of_service: #terms_of_service,
+            contact: #contact,
+            license: #license,
+            ..Default::default()
+        };
+        #(#extensions)*
+        info
+    }}
+}
+
+/// Get a package environment variable, `None` if it is not set or empty.
+fn env_var(key: &str) -> Option<String> {
+    std::env::var(key).ok().filter(|value| !value.is_empty())
+}
+
+fn opt_string(value: &Option<String>) -> TokenStream2 {
+    match value {
+        Some(value) => quote! { Some(#value.to_owned()) },
+        None => quote! { None },
+    }
+}
+
+/// Parse the `CARGO_PKG_AUTHORS` (`Name <email>` separated by `:`) into a contact name and email.
+/// The names of all authors are listed, the email is the one of the first author that has one.
+fn parse_authors(authors: &str) -> (Option<String>, Option<String>) {
+    let mut names = Vec::new();
+    let mut email = None;
+    for author in authors.split(':') {
+        let (name, author_email) = match author.split_once('<') {
+            Some((name, rest)) => (name.trim(), rest.split_once('>').map(|(e, _)| e.trim())),
+            None => (author.trim(), None),
+        };
+        if !name.is_empty() {
+            names.push(name);
+        }
+        if email.is_none() {
+            email = author_email.filter(|e| !e.is_empty()).map(str::to_owned);
+        }
+    }
+    let name = if names.is_empty() {
+        None
+    } else {
+        Some(names.join(", "))
+    };
+    (name, email)
+}
+
+/// Link to the SPDX page of the license, if the license expression is a single identifier.
+fn license_url(license: &str) -> Option<String> {
+    let is_identifier = !license.is_empty()
+        && license
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.');
+    if is_identifier {
+        Some(format!("https://spdx.org/licenses/{license}.html"))
+    } else {
+        None
+    }
+}
+
+/// Parse the `[package.metadata.okapi]` table of a `Cargo.toml`.
+fn parse_okapi_metadata(manifest: &str) -> Result<OkapiMetadata> {
+    let manifest: toml::Table = manifest
+        .parse()
+        .map_err(|err| metadata_error(&format!("Could not parse `Cargo.toml`: {err}")))?;
+    let table = match manifest
+        .get("package")
+        .and_then(|package| package.get("metadata"))
+        .and_then(|metadata| metadata.get("okapi"))
+    {
+        Some(toml::Value::Table(table)) => table,
+        Some(_) => {
+            return Err(metadata_error(
+                "`package.metadata.okapi` should be a table.",
+            ))
+        }
+        None => return Ok(OkapiMetadata::default()),
+    };
+    let mut metadata = OkapiMetadata {
+        terms_of_service: get_string(table, "terms_of_service")?,
+        ..Default::default()
+    };
+    metadata.logo = match table.get("logo") {
+        Some(toml::Value::String(url)) => Some(Logo {
+            url: url.clone(),
+            ..Default::default()
+        }),
+        Some(toml::Value::Table(logo)) => Some(Logo {
+            url: get_string(logo, "url")?
+                .ok_or_else(|| metadata_error("`logo` requires an `url`."))?,
+            alt_text: get_string(logo, "alt_text")?,
+            background_color: get_string(logo, "background_color")?,
+            href: get_string(logo, "href")?,
+        }),
+        Some(_) => return Err(metadata_error("`logo` should be a string or a table.")),
+        None => None,
+    };
+    match table.get("servers") {
+        Some(toml::Value::Array(servers)) => {
+            for server in servers {
+                let server = match server {
+                    toml::Value::String(url) => ServerMetadata {
+                        url: url.clone(),
+                        ..Default::default()
+                    },
+                    toml::Value::Table(server) => ServerMetadata {
+                        url: get_string(server, "url")?
+                            .ok_or_else(|| metadata_error("Every server requires an `url`."))?,
+                        description: get_string(server, "description")?,
+                    },
+                    _ => return Err(metadata_error("A server should be a string or a table.")),
+                };
+                metadata.servers.push(server);
+            }
+        }
+        Some(_) => return Err(metadata_error("`servers` should be an array.")),
+        None => {}
+    }
+    Ok(metadata)
+}
+
+fn get_string(table: &toml::Table, key: &str) -> Result<Option<String>> {
+    match table.get(key) {
+        Some(toml::Value::String(value)) => Ok(Some(value.clone())),
+        Some(_) => Err(metadata_error(&format!("`{key}` should be a string."))),
+        None => Ok(None),
+    }
+}
+
+fn metadata_error(msg: &str) -> Error {
+    Error::new(
+        Span::call_site(),
+        format!("Invalid `[package.metadata.okapi]`: {msg}"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_authors() {
+        assert_eq!(
+            parse_authors("Graham Esau <gesau@hotmail.co.uk>:Ronan Le Meillat - SCTG Development"),
+            (
+                Some("Graham Esau, Ronan Le Meillat - SCTG Development".to_owned()),
+                Some("gesau@hotmail.co.uk".to_owned())
+            )
+        );
+        assert_eq!(parse_authors(""), (None, None));
+    }
+
+    #[test]
+    fn test_license_url() {
+        assert_eq!(
+            license_url("MIT").as_deref(),
+            Some("https://spdx.org/licenses/MIT.html")
+        );
+        assert_eq!(license_url("MIT OR Apache-2.0"), None);
+    }
+
+    #[test]
+    fn test_parse_okapi_metadata() {
+        let manifest = r##"
+            [package]
+            name = "example"
+
+            [package.metadata.okapi]
+            terms_of_service = "https://example.com/terms"
+            logo = { url = "https://example.com/logo.png", background_color = "#FFFFFF" }
+            servers = ["http://127.0.0.1:8000", { url = "https://api.example.com", description = "Production" }]
+        "##;
+        let metadata = parse_okapi_metadata(manifest).unwrap();
+        assert_eq!(
+            metadata.terms_of_service.as_deref(),
+            Some("https://example.com/terms")
+        );
+        let logo = metadata.logo.unwrap();
+        assert_eq!(logo.url, "https://example.com/logo.png");
+        assert_eq!(logo.background_color.as_deref(), Some("#FFFFFF"));
+        assert_eq!(metadata.servers.len(), 2);
+        assert_eq!(
+            metadata.servers[1].description.as_deref(),
+            Some("Production")
+        );
+    }
+
+    #[test]
+    fn test_parse_okapi_metadata_errors() {
+        assert_eq!(
+            parse_okapi_metadata("[package]\nname = \"example\"").unwrap(),
+            OkapiMetadata::default()
+        );
+        assert!(parse_okapi_metadata("[package.metadata.okapi]\nservers = 1").is_err());
+        assert!(parse_okapi_metadata("[package.metadata.okapi]\nlogo = {}").is_err());
+    }
+}
@@ -0,0 +1,35 @@
+use crate::get_add_catcher_fn_name;
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse::Parser, punctuated::Punctuated, token::Comma, Path, Result};
+
+/// Parses catchers and returns a `Vec<rocket_okapi::settings::OpenApiCatcher>`.
+pub fn parse_catchers(catchers: TokenStream) -> Result<TokenStream2> {
+    parse_catchers_ts(catchers.into())
+}
+
+pub(crate) fn parse_catchers_ts(catchers: TokenStream2) -> Result<TokenStream2> {
+    let paths = <Punctuated<Path, Comma>>::parse_terminated.parse2(catchers)?;
+    let add_catchers = paths.into_iter().map(|mut path| {
+        let last_seg = path.segments.last_mut().expect("syn::Path has segments");
+        last_seg.ident = get_add_catcher_fn_name(&last_seg.ident);
+        quote! { #path as ::rocket_okapi::settings::OpenApiCatcher }
+    });
+    Ok(quote! {
+        vec![#(#add_catchers),*]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_catchers() {
+        let tokens = parse_catchers_ts(quote!(crate::not_found, default_catcher)).unwrap();
+        let out = tokens.to_string();
+        assert!(out.contains("okapi_add_catcher_for_not_found_"));
+        assert!(out.contains("okapi_add_catcher_for_default_catcher_"));
+    }
+}
@@ -0,0 +1,265 @@
+//! Implementation of `#[derive(OpenApiResponder)]`, which implements `OpenApiResponderInner` for a
+//! type that derives Rocket's `Responder`, using the same `#[response(...)]` attributes.
+
+use crate::openapi_attr::doc_attr::get_description_from_doc;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_quote, Attribute, Data, DeriveInput, Error, Field, Fields, GenericParam, LitInt, LitStr,
+    Result, Type,
+};
+
+/// The `#[response(status = ..., content_type = "...")]` attribute of a struct or variant.
+#[derive(Default)]
+struct ItemAttrs {
+    status: Option<u16>,
+    content_type: Option<String>,
+}
+
+pub fn derive(mut input: DeriveInput) -> Result<TokenStream2> {
+    let items: Vec<(&[Attribute], &Fields)> = match &input.data {
+        Data::Struct(data) => vec![(&input.attrs[..], &data.fields)],
+        Data::Enum(data) => data
+            .variants
+            .iter()
+            .map(|variant| (&variant.attrs[..], &variant.fields))
+            .collect(),
+        Data::Union(_) => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "`OpenApiResponder` can only be derived for structs and enums.",
+            ))
+        }
+    };
+
+    let mut responder_types = Vec::new();
+    let mut item_responses = Vec::new();
+    for (attrs, fields) in items {
+        let item_attrs = parse_item_attrs(attrs)?;
+        let mut fields = fields.iter();
+        let Some(responder) = fields.next() else {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "`OpenApiResponder` needs at least one field, the responder.",
+            ));
+        };
+        let ty = &responder.ty;
+        responder_types.push(ty.clone());
+
+        // The other fields are headers, unless they are ignored.
+        let mut headers = Vec::new();
+        for field in fields {
+            if is_ignored(field)? {
+                continue;
+            }
+            let Some(name) = header_name(field) else {
+                continue;
+            };
+            let description = get_description_from_doc(&field.attrs)
+                .unwrap_or_else(|| format!("The `{name}` header."));
+            headers.push(quote! {
+                ::rocket_okapi::util::add_success_response_header(
+                    &mut responses,
+                    #name,
+                    ::rocket_okapi::util::string_header(#description, true, None),
+                )?;
+            });
+        }
+        let content_type = item_attrs.content_type.map(|content_type| {
+            quote! {
+                ::rocket_okapi::util::set_success_content_type(&mut responses, #content_type)?;
+            }
+        });
+        let description = get_description_from_doc(attrs).map(|description| {
+            quote! {
+                ::rocket_okapi::util::set_success_response_description(
+                    &mut responses,
+                    #description,
+                )?;
+            }
+        });
+        let status = item_attrs.status.map(|status| {
+            quote! {
+                ::rocket_okapi::util::set_status_code(&mut responses, #status)?;
+            }
+        });
+        // Same order as Rocket: the headers are set on the response of the responder before the
+        // status is changed.
+        item_responses.push(quote! {{
+            let mut responses =
+                <#ty as ::rocket_okapi::response::OpenApiResponderInner>::responses(gen)?;
+            gen.resolve_response_refs(&mut responses);
+            #content_type
+            #(#headers)*
+            #description
+            #status
+            responses
+        }});
+    }
+
+    // The responders that use type parameters have to be documented as well.
+    let has_type_params = input
+        .generics
+        .params
+        .iter()
+        .any(|param| matches!(param, GenericParam::Type(_)));
+    if has_type_params {
+        let where_clause = input.generics.make_where_clause();
+        for ty in &responder_types {
+            where_clause
+                .predicates
+                .push(parse_quote!(#ty: ::rocket_okapi::response::OpenApiResponderInner));
+        }
+    }
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::rocket_okapi::response::OpenApiResponderInner
+            for #ident #ty_generics #where_clause
+        {
+            fn responses(
+                gen: &mut ::rocket_okapi::gen::OpenApiGenerator,
+            ) -> ::rocket_okapi::Result<::rocket_okapi::okapi::openapi3::Responses> {
+                let mut responses = ::rocket_okapi::okapi::openapi3::Responses::default();
+                #(
+                    responses = ::rocket_okapi::util::produce_any_responses(
+                        responses,
+                        #item_responses,
+                    )?;
+                )*
+                Ok(responses)
+            }
+        }
+    })
+}
+
+/// Parse the `#[response(status = 404, content_type = "json")]` attribute of a struct or variant.
+/// The content type can be a media type or a shorthand like `json`, like in Rocket.
+fn parse_item_attrs(attrs: &[Attribute]) -> Result<ItemAttrs> {
+    let mut item_attrs = ItemAttrs::default();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("response")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("status") {
+                let status: LitInt = meta.value()?.parse()?;
+                item_attrs.status = Some(status.base10_parse()?);
+            } else if meta.path.is_ident("content_type") {
+                let content_type: LitStr = meta.value()?.parse()?;
+                let parsed = rocket_http::ContentType::parse_flexible(&content_type.value())
+                    .ok_or_else(|| Error::new_spanned(&content_type, "Invalid content type."))?;
+                item_attrs.content_type = Some(parsed.to_string());
+            } else {
+                return Err(meta.error("Expected `status` or `content_type`."));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(item_attrs)
+}
+
+/// Returns `true` if the field has a `#[response(ignore)]` attribute.
+fn is_ignored(field: &Field) -> Result<bool> {
+    let mut ignore = false;
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("response"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("ignore") {
+                ignore = true;
+                Ok(())
+            } else {
+                Err(meta.error("Expected `ignore`."))
+            }
+        })?;
+    }
+    Ok(ignore)
+}
+
+/// The name of the header that is set by a field. `ContentType` fields set `Content-Type`,
+/// other headers are named after the field (`x_request_id` is `X-Request-Id`) or, for tuple
+/// fields, after the type (`XRequestId`). The name of a `Header` in a tuple field is only known at
+/// runtime.
+fn header_name(field: &Field) -> Option<String> {
+    let type_name = match &field.ty {
+        Type::Path(path) => path.path.segments.last().map(|s| s.ident.to_string()),
+        _ => None,
+    };
+    if type_name.as_deref() == Some("ContentType") {
+        return Some("Content-Type".to_owned());
+    }
+    if let Some(ident) = &field.ident {
+        let name = ident.to_string();
+        return Some(header_case(name.trim_start_matches("r#").split('_')));
+    }
+    match type_name.as_deref() {
+        None | Some("Header") => None,
+        Some(type_name) => Some(header_case(split_camel_case(type_name).into_iter())),
+    }
+}
+
+fn header_case<'a>(words: impl Iterator<Item = &'a str>) -> String {
+    words
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            let first = chars.next().map(|c| c.to_ascii_uppercase());
+            first
+                .into_iter()
+                .chain(chars.map(|c| c.to_ascii_lowercase()))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Split `XRequestId` in `X`, `Request` and `Id`.
+fn split_camel_case(name: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    let mut start = 0;
+    for (i, c) in name.char_indices().skip(1) {
+        if c.is_ascii_uppercase() {
+            words.push(&name[start..i]);
+            start = i;
+        }
+    }
+    words.push(&name[start..]);
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_item_attrs() {
+        let attrs: Vec<Attribute> = vec![
+            parse_quote!(#[doc = " Not found."]),
+            parse_quote!(#[response(status = 404, content_type = "json")]),
+        ];
+        let item_attrs = parse_item_attrs(&attrs).unwrap();
+        assert_eq!(item_attrs.status, Some(404));
+        assert_eq!(item_attrs.content_type.as_deref(), Some("application/json"));
+        let attrs: Vec<Attribute> = vec![parse_quote!(#[response(content_type = "no type")])];
+        assert!(parse_item_attrs(&attrs).is_err());
+    }
+
+    #[test]
+    fn test_header_name() {
+        let fields: syn::FieldsNamed = parse_quote!({
+            x_request_id: Header<'static>,
+            kind: ContentType,
+            #[response(ignore)]
+            skipped: u8,
+        });
+        let fields: Vec<_> = fields.named.into_iter().collect();
+        assert_eq!(header_name(&fields[0]).as_deref(), Some("X-Request-Id"));
+        assert_eq!(header_name(&fields[1]).as_deref(), Some("Content-Type"));
+        assert!(is_ignored(&fields[2]).unwrap());
+
+        let fields: syn::FieldsUnnamed = parse_quote!((String, XRequestId, Header<'static>));
+        let fields: Vec<_> = fields.unnamed.into_iter().collect();
+        assert_eq!(header_name(&fields[1]).as_deref(), Some("X-Request-Id"));
+        assert_eq!(header_name(&fields[2]), None);
+    }
+}
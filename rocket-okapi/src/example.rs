@@ -0,0 +1,119 @@
+use crate::{OpenApiError, Result};
+use serde::Serialize;
+use serde_json::Value;
+use std::marker::PhantomData;
+
+/// Implementing this trait adds an example of the type to the documented request bodies and
+/// responses that contain it, for example a `Json<T>` request body or a `Result<Json<T>, E>`
+/// response.
+///
+/// Examples set with `#[openapi(request_example = ..., response_example(...))]` take precedence
+/// over this trait. Types without an implementation fall back to the first of the `examples` of
+/// their schema, like the ones added with `#[schemars(example = ...)]`.
+///
+/// ```rust
+/// use rocket_okapi::example::OpenApiExample;
+///
+/// struct User {
+///     id: u64,
+///     name: String,
+/// }
+///
+/// impl OpenApiExample for User {
+///     fn example() -> serde_json::Value {
+///         serde_json::json!({ "id": 1, "name": "Alice" })
+///     }
+/// }
+/// ```
+pub trait OpenApiExample {
+    /// An example value of the type, as it is serialized in a request or response body.
+    fn example() -> Value;
+}
+
+macro_rules! forward_example {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl<T: OpenApiExample> OpenApiExample for $ty {
+                fn example() -> Value {
+                    T::example()
+                }
+            }
+        )*
+    };
+}
+
+forward_example!(
+    Box<T>,
+    Option<T>,
+    rocket::serde::json::Json<T>,
+    rocket::form::Form<T>,
+    rocket::response::status::Created<T>,
+    rocket::response::status::Accepted<T>,
+    rocket::response::status::Custom<T>,
+);
+
+#[cfg(feature = "msgpack")]
+forward_example!(rocket::serde::msgpack::MsgPack<T>);
+
+impl<T: OpenApiExample, E> OpenApiExample for std::result::Result<T, E> {
+    fn example() -> Value {
+        T::example()
+    }
+}
+
+impl<T: OpenApiExample> OpenApiExample for Vec<T> {
+    fn example() -> Value {
+        Value::Array(vec![T::example()])
+    }
+}
+
+/// Used by the `#[openapi]` macro to get the example of a type, if the type implements
+/// `OpenApiExample`, through `(&&ExampleProbe::<T>::new()).probe_example()`.
+#[doc(hidden)]
+pub struct ExampleProbe<T: ?Sized>(PhantomData<T>);
+
+impl<T: ?Sized> ExampleProbe<T> {
+    #[doc(hidden)]
+    #[must_use]
+    pub fn new() -> Self {
+        ExampleProbe(PhantomData)
+    }
+}
+
+impl<T: ?Sized> Default for ExampleProbe<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Used when the probed type implements `OpenApiExample`.
+#[doc(hidden)]
+pub trait ViaOpenApiExample {
+    #[doc(hidden)]
+    fn probe_example(&self) -> Option<Value>;
+}
+
+impl<T: OpenApiExample + ?Sized> ViaOpenApiExample for &ExampleProbe<T> {
+    fn probe_example(&self) -> Option<Value> {
+        Some(T::example())
+    }
+}
+
+/// Used when the probed type does not implement `OpenApiExample`.
+#[doc(hidden)]
+pub trait ViaNoExample {
+    #[doc(hidden)]
+    fn probe_example(&self) -> Option<Value>;
+}
+
+impl<T: ?Sized> ViaNoExample for ExampleProbe<T> {
+    fn probe_example(&self) -> Option<Value> {
+        None
+    }
+}
+
+/// Serializes `value` into an example, for the examples of the `#[openapi]` attribute.
+pub fn to_example<T: Serialize + ?Sized>(value: &T) -> Result<Value> {
+    serde_json::to_value(value)
+        .map_err(|e| OpenApiError::new(format!("Could not serialize example: {e}")))
+}
@@ -1,24 +1,48 @@
-use crate::settings::OpenApiSettings;
-use crate::OperationInfo;
-use okapi::openapi3::{Components, OpenApi, Operation, PathItem, RefOr, SecurityScheme};
+use crate::request::form_schema::FileSchemas;
+use crate::settings::{
+    CatcherResponses, OpenApiSettings, OperationIdInput, RankedRoutes, SchemaUnions,
+};
+use crate::util::{self, default_description, set_default_descriptions};
+use crate::{CatcherInfo, ChannelInfo, ChannelProtocol, OpenApiError, OperationInfo, Result};
+use okapi::asyncapi::{self, AsyncApi, ChannelItem, Message};
+use okapi::openapi3::{
+    Components, ExternalDocs, Header, Info, MediaType, Object, OpenApi, Operation, Parameter,
+    ParameterValue, PathItem, Ref, RefOr, RequestBody, Response, Responses, SchemaObject,
+    SecurityScheme, Server,
+};
 use okapi::{Map, MapEntry};
+use rocket::data::ByteUnit;
 use rocket::http::Method;
-use schemars::generate::SchemaGenerator;
+use schemars::consts::meta_schemas;
+use schemars::generate::{GenTransform, SchemaGenerator, SchemaSettings};
+use schemars::transform::{
+    AddNullable, RemoveRefSiblings, ReplaceBoolSchemas, ReplaceConstValue, ReplacePrefixItems,
+    ReplaceUnevaluatedProperties, SetSingleExample,
+};
 use schemars::JsonSchema;
-use schemars::Schema as SchemaObject;
-use std::collections::HashMap;
+use serde_json::Value;
+use std::collections::hash_map::{Entry as HashMapEntry, HashMap};
+use std::collections::HashSet;
 
 /// A struct that visits all `rocket::Route`s, and aggregates information about them.
 #[derive(Debug, Clone)]
 pub struct OpenApiGenerator {
-    // TODO: This tag should be removed in the future and settings should be used.
-    // This `allow` is just added in the mean time to make sure alle other test
-    // will be finished correctly.
-    #[allow(dead_code)]
     settings: OpenApiSettings,
     schema_generator: SchemaGenerator,
     security_schemes: Map<String, SecurityScheme>,
     operations: Map<String, HashMap<Method, Operation>>,
+    channels: Map<String, ChannelItem>,
+    info: Info,
+    external_docs: Option<ExternalDocs>,
+    servers: Vec<Server>,
+    /// The route (method and path) that uses each `operationId`.
+    operation_ids: HashMap<String, (Method, String)>,
+    /// The routes (method and path) of which all documented routes declare a `format`.
+    formatted_routes: HashSet<(Method, String)>,
+    /// The documented catchers, in the order they were added.
+    catchers: Vec<Catcher>,
+    /// The reusable responses, parameters, headers and request bodies.
+    components: ComponentRegistry,
 }
 
 impl OpenApiGenerator {
@@ -26,40 +50,332 @@ impl OpenApiGenerator {
     #[must_use]
     pub fn new(settings: &OpenApiSettings) -> Self {
         OpenApiGenerator {
-            schema_generator: settings.schema_settings.clone().into_generator(),
+            schema_generator: schema_settings(settings).into_generator(),
             settings: settings.clone(),
             security_schemes: Map::default(),
             operations: Map::default(),
+            channels: Map::default(),
+            info: Info::default(),
+            external_docs: None,
+            servers: Vec::new(),
+            operation_ids: HashMap::new(),
+            formatted_routes: HashSet::new(),
+            catchers: Vec::new(),
+            components: ComponentRegistry::default(),
         }
     }
 
+    /// Create the `operationId` of a route using the [`OpenApiSettings::operation_id_strategy`].
+    #[must_use]
+    pub fn operation_id(&self, input: &OperationIdInput<'_>) -> String {
+        self.settings.operation_id_strategy.operation_id(input)
+    }
+
+    /// The configured size limit of request bodies with the given name, like `json` or `file`.
+    /// Returns `None` if [`OpenApiSettings::body_limits`] is not set.
+    #[must_use]
+    pub fn body_limit(&self, name: &str) -> Option<ByteUnit> {
+        self.settings.body_limits.as_ref()?.get(name)
+    }
+
+    /// Set the `Info` of the generated specification, normally from the package information of
+    /// the crate. [`OpenApiSettings::info`] takes precedence over this.
+    pub fn set_info(&mut self, info: Info) {
+        self.info = info;
+    }
+
+    /// Set the external documentation of the generated specification, normally the homepage of
+    /// the crate.
+    pub fn set_external_docs(&mut self, external_docs: Option<ExternalDocs>) {
+        self.external_docs = external_docs;
+    }
+
+    /// Set the servers of the generated specification, normally from the
+    /// `[package.metadata.okapi]` table of the crate. These are only used when
+    /// [`OpenApiSettings::servers`] is empty.
+    pub fn set_servers(&mut self, servers: Vec<Server>) {
+        self.servers = servers;
+    }
+
     /// Adds/Replace a security scheme to the generated output
     pub fn add_security_scheme(&mut self, name: String, scheme: SecurityScheme) {
         self.security_schemes.insert(name, scheme);
     }
 
+    /// Register a reusable response, which is added to `components.responses` with the given
+    /// name.
+    ///
+    /// The returned response can be used like any other response, also by the functions in
+    /// `rocket_okapi::util`. It is replaced by a `$ref` to the component when the specification is
+    /// generated, unless it was changed, like a copy-on-write.
+    ///
+    /// Registering the same response again under a name is allowed, but this returns an error
+    /// when a different response is already registered with that name.
+    pub fn register_response(
+        &mut self,
+        name: impl Into<String>,
+        response: Response,
+    ) -> Result<RefOr<Response>> {
+        register(&mut self.components.responses, name.into(), response).map(RefOr::Object)
+    }
+
+    /// Register a reusable parameter, which is added to `components.parameters` with the given
+    /// name. Like [`OpenApiGenerator::register_response`], the returned parameter is replaced by
+    /// a `$ref` unless it was changed, and this returns an error when a different parameter is
+    /// already registered with that name.
+    pub fn register_parameter(
+        &mut self,
+        name: impl Into<String>,
+        parameter: Parameter,
+    ) -> Result<Parameter> {
+        register(&mut self.components.parameters, name.into(), parameter)
+    }
+
+    /// Register a reusable response header, which is added to `components.headers` with the
+    /// given name. Like [`OpenApiGenerator::register_response`], the returned header is replaced
+    /// by a `$ref` unless it was changed, and this returns an error when a different header is
+    /// already registered with that name.
+    pub fn register_header(
+        &mut self,
+        name: impl Into<String>,
+        header: Header,
+    ) -> Result<RefOr<Header>> {
+        register(&mut self.components.headers, name.into(), header).map(RefOr::Object)
+    }
+
+    /// Register a reusable request body, which is added to `components.requestBodies` with the
+    /// given name. Like [`OpenApiGenerator::register_response`], the returned request body is
+    /// replaced by a `$ref` unless it was changed, and this returns an error when a different
+    /// request body is already registered with that name.
+    pub fn register_request_body(
+        &mut self,
+        name: impl Into<String>,
+        request_body: RequestBody,
+    ) -> Result<RequestBody> {
+        register(
+            &mut self.components.request_bodies,
+            name.into(),
+            request_body,
+        )
+    }
+
+    /// Replaces the `$ref`s to responses registered with [`OpenApiGenerator::register_response`]
+    /// by a copy of the response, like the one returned by `register_response`, so the functions
+    /// in `rocket_okapi::util` can change them. Copies that stay unchanged become a `$ref` again
+    /// when the specification is generated.
+    ///
+    /// This is done for the responses of all responders and guards of a route. Other `$ref`s are
+    /// left alone.
+    pub fn resolve_response_refs(&self, responses: &mut Responses) {
+        for response in responses.responses.values_mut() {
+            if let RefOr::Ref(reference) = response {
+                if let Some(resolved) = resolve(&self.components.responses, &reference.reference) {
+                    *response = RefOr::Object(resolved);
+                }
+            }
+        }
+    }
+
     /// Add a new `HTTP Method` to the collection of endpoints in the `OpenApiGenerator`.
-    pub fn add_operation(&mut self, mut op: OperationInfo) {
+    ///
+    /// When an operation with the same path and method was already added, the two are combined
+    /// as configured by [`OpenApiSettings::ranked_routes`]. This returns an error when that is
+    /// set to [`RankedRoutes::Error`], or when the `operationId` is already used by another route.
+    pub fn add_operation(&mut self, mut op: OperationInfo) -> Result<()> {
         if let Some(op_id) = op.operation.operation_id {
             // TODO do this outside add_operation
             op.operation.operation_id = Some(op_id.trim_start_matches(':').replace("::", "_"));
         }
-        match self.operations.entry(op.path) {
-            MapEntry::Occupied(mut e) => {
-                let map = e.get_mut();
-                if map.insert(op.method, op.operation).is_some() {
-                    // This will trow a warning if 2 routes have the same path and method
-                    // This is allowed by Rocket when a ranking is given for example: `#[get("/user", rank = 2)]`
-                    // See: https://rocket.rs/v0.4/guide/requests/#forwarding
-                    println!("Warning: Operation replaced for {}:{}", op.method, e.key());
+        let map = self.operations.entry(op.path.clone()).or_default();
+        let existing = match map.entry(op.method) {
+            HashMapEntry::Occupied(e) => e.into_mut(),
+            HashMapEntry::Vacant(e) => {
+                if let Some(op_id) = &op.operation.operation_id {
+                    match self.operation_ids.entry(op_id.clone()) {
+                        HashMapEntry::Occupied(used_by) => {
+                            let (method, path) = used_by.get();
+                            return Err(OpenApiError::new(format!(
+                                "The operationId `{op_id}` is used by both `{method} {path}` and `{} {}`.",
+                                op.method, op.path
+                            )));
+                        }
+                        HashMapEntry::Vacant(used_by) => {
+                            used_by.insert((op.method, op.path.clone()));
+                        }
+                    }
                 }
+                if op.format.is_some() {
+                    self.formatted_routes.insert((op.method, op.path));
+                }
+                e.insert(op.operation);
+                return Ok(());
             }
-            MapEntry::Vacant(e) => {
-                let mut map = HashMap::new();
-                map.insert(op.method, op.operation);
-                e.insert(map);
+        };
+        // Routes that only differ by `format` are documented as one operation.
+        let route = (op.method, op.path.clone());
+        if op.format.is_some()
+            && self.formatted_routes.contains(&route)
+            && merge_format_variants(existing, &op.operation)
+        {
+            return Ok(());
+        }
+        // Later routes are only merged by `format` when all documented routes declare one.
+        self.formatted_routes.remove(&route);
+        // Rocket allows routes with the same path and method when they have a different rank,
+        // for example: `#[get("/user", rank = 2)]`
+        // See: https://rocket.rs/v0.5/guide/requests/#forwarding
+        match self.settings.ranked_routes {
+            RankedRoutes::Merge => merge_ranked_operations(existing, op.operation),
+            RankedRoutes::KeepFirst => Ok(()),
+            RankedRoutes::Error => Err(OpenApiError::new(format!(
+                "Multiple routes are documented for `{} {}`.",
+                op.method, op.path
+            ))),
+        }
+    }
+
+    /// Add the response of a Rocket catcher. It is added to `components.responses` and referenced
+    /// by the operations as configured by [`OpenApiSettings::catcher_responses`].
+    ///
+    /// The catcher always responds with the status code it handles, so when its return type
+    /// documents several responses, their content and headers are combined into one response.
+    /// The response is named after the catcher function, or after its module qualified path when
+    /// catchers in different modules have the same name.
+    pub fn add_catcher(&mut self, mut info: CatcherInfo) -> Result<()> {
+        self.resolve_response_refs(&mut info.responses);
+        let mut response = Response::default();
+        for (_, other) in info.responses.responses {
+            let other = match other {
+                RefOr::Object(other) => other,
+                RefOr::Ref(_) => {
+                    return Err(OpenApiError::new(
+                        "Catchers with Ref responses are not supported.".to_owned(),
+                    ))
+                }
+            };
+            if response.description.is_empty() {
+                response.description = other.description;
             }
+            for (name, header) in other.headers {
+                response.headers.entry(name).or_insert(header);
+            }
+            merge_content(&mut response.content, other.content);
+        }
+        if let Some(description) = info.description {
+            response.description = description;
+        }
+        self.catchers
+            .retain(|c| c.name != info.name || c.module_path != info.module_path);
+        self.catchers.push(Catcher {
+            name: info.name,
+            module_path: info.module_path,
+            status: info.status,
+            response,
+        });
+        Ok(())
+    }
+
+    /// Add a new WebSocket or Server-Sent Events endpoint to the channels of the AsyncAPI
+    /// document.
+    ///
+    /// Endpoints with the same path are combined into one channel, for example a route that only
+    /// sends and a route that only receives messages. This returns an error when both send or
+    /// both receive messages.
+    pub fn add_channel(&mut self, info: ChannelInfo) -> Result<()> {
+        // Only path parameters are part of the channel name.
+        let parameters = info
+            .parameters
+            .into_iter()
+            .filter_map(|param| match param {
+                RefOr::Object(param) if param.location == "path" => {
+                    let schema = match param.value {
+                        ParameterValue::Schema { schema, .. } => Some(schema),
+                        ParameterValue::Content { .. } => None,
+                    };
+                    let channel_param = asyncapi::Parameter {
+                        description: param.description,
+                        schema,
+                        ..Default::default()
+                    };
+                    Some((param.name, channel_param.into()))
+                }
+                _ => None,
+            })
+            .collect();
+        let message = |schema: Option<SchemaObject>| {
+            schema.map(|payload| {
+                Message {
+                    payload: Some(payload),
+                    content_type: Some("application/json".to_owned()),
+                    ..Default::default()
+                }
+                .into()
+            })
+        };
+        let operation = |message: Option<RefOr<Message>>, suffix: &str| {
+            message.map(|message| asyncapi::Operation {
+                operation_id: info
+                    .operation_id
+                    .as_ref()
+                    .map(|id| format!("{id}_{suffix}")),
+                summary: info.summary.clone(),
+                description: info.description.clone(),
+                message: Some(message),
+                ..Default::default()
+            })
         };
+        // AsyncAPI 2.x describes operations from the client's point of view:
+        // the client subscribes to what the server sends and publishes what it receives.
+        let mut subscribe = operation(message(info.send), "send");
+        let publish = operation(message(info.receive), "receive");
+        let mut bindings = Object::default();
+        match info.protocol {
+            ChannelProtocol::WebSocket => {
+                bindings.insert("ws".to_owned(), serde_json::json!({ "method": "GET" }));
+            }
+            ChannelProtocol::EventStream => {
+                if let Some(subscribe) = subscribe.as_mut() {
+                    subscribe.bindings.insert(
+                        "http".to_owned(),
+                        serde_json::json!({ "type": "request", "method": "GET" }),
+                    );
+                }
+            }
+        }
+        let channel = ChannelItem {
+            description: info.description,
+            parameters,
+            subscribe,
+            publish,
+            bindings,
+            ..Default::default()
+        };
+        match self.channels.entry(info.path) {
+            MapEntry::Vacant(entry) => {
+                entry.insert(channel);
+            }
+            MapEntry::Occupied(mut entry) => {
+                let existing = entry.get_mut();
+                if (existing.subscribe.is_some() && channel.subscribe.is_some())
+                    || (existing.publish.is_some() && channel.publish.is_some())
+                {
+                    return Err(OpenApiError::new(format!(
+                        "Multiple routes send or receive messages on the channel `{}`.",
+                        entry.key()
+                    )));
+                }
+                existing.description = existing.description.take().or(channel.description);
+                existing.subscribe = existing.subscribe.take().or(channel.subscribe);
+                existing.publish = existing.publish.take().or(channel.publish);
+                for (name, parameter) in channel.parameters {
+                    existing.parameters.entry(name).or_insert(parameter);
+                }
+                for (name, binding) in channel.bindings {
+                    existing.bindings.entry(name).or_insert(binding);
+                }
+            }
+        }
+        Ok(())
     }
 
     /// Returns a JSON Schema object for the type `T`.
@@ -67,6 +383,13 @@ impl OpenApiGenerator {
         self.schema_generator.subschema_for::<T>()
     }
 
+    /// Returns the schema of binary data, like the body of a file: a string with
+    /// `format: binary` for OpenAPI 3.0, or with `contentMediaType` for OpenAPI 3.1.
+    #[must_use]
+    pub fn binary_schema(&self) -> SchemaObject {
+        binary_schema(&self.settings.openapi_version)
+    }
+
     /// Obtain the internal `SchemaGenerator` object.
     #[must_use]
     pub fn schema_generator(&self) -> &SchemaGenerator {
@@ -81,6 +404,9 @@ impl OpenApiGenerator {
     /// Generate an `OpenApi` specification for all added operations.
     #[must_use]
     pub fn into_openapi(self) -> OpenApi {
+        let settings = self.settings;
+        let components = self.components;
+        let catchers = name_catchers(self.catchers);
         let mut schema_generator = self.schema_generator;
         let schemas = schema_generator.take_definitions(true);
 
@@ -93,11 +419,26 @@ impl OpenApiGenerator {
         // `take_definitions(true)` has already applied transforms; no need to manually call visitors
 
         OpenApi {
-            openapi: "3.0.0".to_owned(),
+            openapi: settings.openapi_version,
+            info: settings.info.unwrap_or(self.info),
+            servers: if settings.servers.is_empty() {
+                self.servers
+            } else {
+                settings.servers
+            },
             paths: {
                 let mut paths = Map::new();
                 for (path, map) in self.operations {
-                    for (method, op) in map {
+                    for (method, mut op) in map {
+                        use_component_refs(&mut op, &components);
+                        add_catcher_responses(&mut op, &catchers, settings.catcher_responses);
+                        for response in op.responses.responses.values_mut() {
+                            if let RefOr::Object(response) = response {
+                                finish_schema_unions(response, &schemas, settings.schema_unions);
+                            }
+                        }
+                        set_default_descriptions(&mut op.responses);
+                        add_schema_examples(&mut op, &schemas);
                         let path_item = paths.entry(path.clone()).or_default();
                         set_operation(path_item, method, op);
                     }
@@ -105,6 +446,65 @@ impl OpenApiGenerator {
                 paths
             },
             components: Some(Components {
+                responses: catchers
+                    .into_iter()
+                    .map(|(name, (status, mut response))| {
+                        if response.description.is_empty() {
+                            response.description = match status {
+                                Some(status) => default_description(&status.to_string()),
+                                None => default_description("default"),
+                            };
+                        }
+                        (name, response)
+                    })
+                    .chain(
+                        components
+                            .responses
+                            .clone()
+                            .into_iter()
+                            .map(|(name, mut response)| {
+                                if response.description.is_empty() {
+                                    response.description = default_description(&name);
+                                }
+                                (name, response)
+                            }),
+                    )
+                    .map(|(name, mut response)| {
+                        finish_schema_unions(&mut response, &schemas, settings.schema_unions);
+                        add_response_examples(&mut response, &schemas);
+                        let mut response = RefOr::Object(response);
+                        use_response_refs(&mut response, &components);
+                        (name, response)
+                    })
+                    .collect(),
+                parameters: components
+                    .parameters
+                    .iter()
+                    .map(|(name, parameter)| {
+                        let mut parameter = parameter.clone();
+                        add_parameter_example(&mut parameter.value, &schemas);
+                        (name.clone(), parameter.into())
+                    })
+                    .collect(),
+                headers: components
+                    .headers
+                    .iter()
+                    .map(|(name, header)| {
+                        let mut header = header.clone();
+                        add_parameter_example(&mut header.value, &schemas);
+                        (name.clone(), header.into())
+                    })
+                    .collect(),
+                request_bodies: components
+                    .request_bodies
+                    .iter()
+                    .map(|(name, request_body)| {
+                        let mut request_body = request_body.clone();
+                        add_media_type_examples(&mut request_body.content, &schemas);
+                        (name.clone(), request_body.into())
+                    })
+                    .collect(),
+                // Last, because the other components use the schemas for their examples.
                 schemas: schemas
                     .into_iter()
                     .map(|(k, v)| (k, v.try_into().unwrap()))
@@ -112,9 +512,806 @@ impl OpenApiGenerator {
                 security_schemes: schemes,
                 ..Default::default()
             }),
-            ..OpenApi::default()
+            security: settings.security,
+            tags: settings.tags,
+            external_docs: self.external_docs,
+            extensions: settings.extensions,
+        }
+    }
+
+    /// Generate the `OpenApi` specification for all added operations and, when there are
+    /// channels, the `AsyncApi` specification for them. Both use the same schemas, which are only
+    /// generated once.
+    #[must_use]
+    pub fn into_openapi_and_asyncapi(mut self) -> (OpenApi, Option<AsyncApi>) {
+        let channels = std::mem::take(&mut self.channels);
+        let openapi = self.into_openapi();
+        if channels.is_empty() {
+            return (openapi, None);
+        }
+        let asyncapi = AsyncApi {
+            asyncapi: AsyncApi::default_version(),
+            info: openapi.info.clone(),
+            channels,
+            components: Some(asyncapi::Components {
+                schemas: openapi
+                    .components
+                    .as_ref()
+                    .map(|components| components.schemas.clone())
+                    .unwrap_or_default(),
+                ..Default::default()
+            }),
+            tags: openapi.tags.clone(),
+            external_docs: openapi.external_docs.clone(),
+            ..AsyncApi::default()
+        };
+        (openapi, Some(asyncapi))
+    }
+
+    /// Generate an `AsyncApi` specification for all added channels.
+    /// The schemas are the same as the ones in the `OpenApi` specification generated from the
+    /// same routes.
+    #[must_use]
+    pub fn into_asyncapi(self) -> AsyncApi {
+        let mut schema_generator = self.schema_generator;
+        let schemas = schema_generator.take_definitions(true);
+
+        AsyncApi {
+            asyncapi: AsyncApi::default_version(),
+            info: self.settings.info.unwrap_or(self.info),
+            channels: self.channels,
+            components: Some(asyncapi::Components {
+                schemas: schemas
+                    .into_iter()
+                    .map(|(k, v)| (k, v.try_into().unwrap()))
+                    .collect(),
+                ..Default::default()
+            }),
+            tags: self.settings.tags,
+            external_docs: self.external_docs,
+            ..AsyncApi::default()
+        }
+    }
+}
+
+/// Removes the marks of the `anyOf` schemas that combine the schemas of several responders. With
+/// [`SchemaUnions::OneOf`] they are replaced by `oneOf` when a `discriminator` can be created or
+/// when the members are references to types that can not overlap.
+fn finish_schema_unions(
+    response: &mut Response,
+    definitions: &serde_json::Map<String, Value>,
+    schema_unions: SchemaUnions,
+) {
+    for media in response.content.values_mut() {
+        if media
+            .extensions
+            .shift_remove(util::SCHEMA_UNION_EXTENSION)
+            .is_none()
+            || schema_unions != SchemaUnions::OneOf
+        {
+            continue;
+        }
+        let Some(object) = media.schema.as_mut().and_then(SchemaObject::as_object_mut) else {
+            continue;
+        };
+        let Some(Value::Array(members)) = object.get("anyOf") else {
+            continue;
+        };
+        // `oneOf` requires that a value matches exactly one member, so it is only used when the
+        // members can be told apart.
+        let discriminator = discriminator(members, definitions);
+        if discriminator.is_none() && !are_disjoint_refs(members, definitions) {
+            continue;
+        }
+        if let Some(members) = object.remove("anyOf") {
+            object.insert("oneOf".to_owned(), members);
+        }
+        if let Some(discriminator) = discriminator {
+            object.insert("discriminator".to_owned(), discriminator);
+        }
+    }
+}
+
+/// Whether all `members` are references to types that no value can match at the same time,
+/// because they have a different `type`, like a struct and a sequence.
+fn are_disjoint_refs(members: &[Value], definitions: &serde_json::Map<String, Value>) -> bool {
+    let mut types: Vec<&str> = Vec::new();
+    for member in members {
+        let Some(definition) = member
+            .get("$ref")
+            .and_then(Value::as_str)
+            .and_then(|reference| definitions.get(reference.rsplit('/').next()?))
+        else {
+            return false;
+        };
+        // Every integer is also a number.
+        let ty = match definition.get("type").and_then(Value::as_str) {
+            Some("integer") => "number",
+            Some(ty) => ty,
+            None => return false,
+        };
+        if types.contains(&ty) {
+            return false;
+        }
+        types.push(ty);
+    }
+    true
+}
+
+/// Creates the `discriminator` of `oneOf` members that are all references to types with the same
+/// tag property, like enums with `#[serde(tag = "...")]`.
+/// Returns `None` if a member is not such a reference or if tag values are used more than once.
+fn discriminator(members: &[Value], definitions: &serde_json::Map<String, Value>) -> Option<Value> {
+    let mut property_name: Option<String> = None;
+    let mut mapping = serde_json::Map::new();
+    for member in members {
+        let reference = member.get("$ref")?.as_str()?;
+        let definition = definitions.get(reference.rsplit('/').next()?)?;
+        // A tagged enum is a `oneOf` of its tagged variants.
+        let variants = match definition.get("oneOf") {
+            Some(Value::Array(variants)) => variants.iter().collect(),
+            _ => vec![definition],
+        };
+        for variant in variants {
+            let (name, value) = tag_property(variant, property_name.as_deref())?;
+            if mapping.insert(value, reference.into()).is_some() {
+                return None;
+            }
+            property_name = Some(name);
         }
     }
+    Some(serde_json::json!({
+        "propertyName": property_name?,
+        "mapping": mapping,
+    }))
+}
+
+/// The name and value of the tag of a tagged struct or enum variant: a required property with a
+/// single string value. When `name` is given, only that property is used.
+fn tag_property(schema: &Value, name: Option<&str>) -> Option<(String, String)> {
+    let properties = schema.get("properties")?.as_object()?;
+    let required = schema.get("required")?.as_array()?;
+    required
+        .iter()
+        .filter_map(Value::as_str)
+        .filter(|required| name.is_none_or(|name| name == *required))
+        .find_map(|required| {
+            let property = properties.get(required)?;
+            let value = match property.get("enum") {
+                Some(Value::Array(values)) if values.len() == 1 => values.first(),
+                _ => property.get("const"),
+            };
+            Some((required.to_owned(), value?.as_str()?.to_owned()))
+        })
+}
+
+/// Adds the examples of the schemas to the parameters, request body and responses
+/// of `op` that do not have an example yet.
+fn add_schema_examples(op: &mut Operation, definitions: &serde_json::Map<String, Value>) {
+    for parameter in &mut op.parameters {
+        if let RefOr::Object(parameter) = parameter {
+            add_parameter_example(&mut parameter.value, definitions);
+        }
+    }
+    if let Some(RefOr::Object(request_body)) = &mut op.request_body {
+        add_media_type_examples(&mut request_body.content, definitions);
+    }
+    for response in op.responses.responses.values_mut() {
+        if let RefOr::Object(response) = response {
+            add_response_examples(response, definitions);
+        }
+    }
+}
+
+fn add_response_examples(response: &mut Response, definitions: &serde_json::Map<String, Value>) {
+    add_media_type_examples(&mut response.content, definitions);
+    for header in response.headers.values_mut() {
+        if let RefOr::Object(header) = header {
+            add_parameter_example(&mut header.value, definitions);
+        }
+    }
+}
+
+fn add_parameter_example(value: &mut ParameterValue, definitions: &serde_json::Map<String, Value>) {
+    match value {
+        ParameterValue::Schema {
+            schema,
+            example,
+            examples,
+            ..
+        } => {
+            if example.is_none() && examples.is_none() {
+                *example = schema_example(schema.as_value(), definitions, &mut Vec::new());
+            }
+        }
+        ParameterValue::Content { content } => add_media_type_examples(content, definitions),
+    }
+}
+
+fn add_media_type_examples(
+    content: &mut Map<String, MediaType>,
+    definitions: &serde_json::Map<String, Value>,
+) {
+    for media in content.values_mut() {
+        if media.example.is_none() && media.examples.is_none() {
+            media.example = media
+                .schema
+                .as_ref()
+                .and_then(|schema| schema_example(schema.as_value(), definitions, &mut Vec::new()));
+        }
+    }
+}
+
+/// The `example` or first of the `examples` of `schema` or of the schema it refers to. For arrays,
+/// this is an array with the example of the items. `visited` holds the references that are already
+/// followed, to stop at recursive types.
+fn schema_example<'a>(
+    schema: &'a Value,
+    definitions: &'a serde_json::Map<String, Value>,
+    visited: &mut Vec<&'a str>,
+) -> Option<Value> {
+    // The OpenAPI 3.0 schemas use `example` instead of `examples`.
+    if let Some(example) = schema.get("example") {
+        return Some(example.clone());
+    }
+    if let Some(examples) = schema.get("examples") {
+        return examples.as_array()?.first().cloned();
+    }
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        if visited.contains(&reference) {
+            return None;
+        }
+        visited.push(reference);
+        let definition = definitions.get(reference.rsplit('/').next()?)?;
+        return schema_example(definition, definitions, visited);
+    }
+    let items = schema.get("items")?;
+    Some(Value::Array(vec![schema_example(
+        items,
+        definitions,
+        visited,
+    )?]))
+}
+
+/// The extension that marks a copy of a registered component with the name of the component.
+const COMPONENT_EXTENSION: &str = "x-okapi-component";
+
+/// The reusable responses, parameters, headers and request bodies, by name.
+#[derive(Debug, Clone, Default)]
+struct ComponentRegistry {
+    responses: Map<String, Response>,
+    parameters: Map<String, Parameter>,
+    headers: Map<String, Header>,
+    request_bodies: Map<String, RequestBody>,
+}
+
+/// An object that can be registered as a reusable component.
+trait Component: Clone + PartialEq {
+    /// The field of `components` that contains these objects.
+    const FIELD: &'static str;
+
+    fn extensions_mut(&mut self) -> &mut Object;
+}
+
+impl Component for Response {
+    const FIELD: &'static str = "responses";
+
+    fn extensions_mut(&mut self) -> &mut Object {
+        &mut self.extensions
+    }
+}
+
+impl Component for Parameter {
+    const FIELD: &'static str = "parameters";
+
+    fn extensions_mut(&mut self) -> &mut Object {
+        &mut self.extensions
+    }
+}
+
+impl Component for Header {
+    const FIELD: &'static str = "headers";
+
+    fn extensions_mut(&mut self) -> &mut Object {
+        &mut self.extensions
+    }
+}
+
+impl Component for RequestBody {
+    const FIELD: &'static str = "requestBodies";
+
+    fn extensions_mut(&mut self) -> &mut Object {
+        &mut self.extensions
+    }
+}
+
+/// Adds `component` to the `registry` and returns a copy that is marked with its name.
+///
+/// Returns an error when a different component is already registered with the same name.
+fn register<T: Component>(registry: &mut Map<String, T>, name: String, component: T) -> Result<T> {
+    if registry
+        .get(&name)
+        .is_some_and(|existing| *existing != component)
+    {
+        return Err(OpenApiError::new(format!(
+            "A different component is already registered in `components.{}` as `{name}`.",
+            T::FIELD
+        )));
+    }
+    let marked = mark(component.clone(), name.clone());
+    registry.insert(name, component);
+    Ok(marked)
+}
+
+/// Marks a copy of a registered component with the name of the component.
+fn mark<T: Component>(mut component: T, name: String) -> T {
+    component
+        .extensions_mut()
+        .insert(COMPONENT_EXTENSION.to_owned(), name.into());
+    component
+}
+
+/// Returns a marked copy of the registered component that `reference` points to, like
+/// `#/components/responses/NotFound`.
+fn resolve<T: Component>(registry: &Map<String, T>, reference: &str) -> Option<T> {
+    let name = reference
+        .strip_prefix("#/components/")?
+        .strip_prefix(T::FIELD)?
+        .strip_prefix('/')?;
+    let component = registry.get(name)?;
+    Some(mark(component.clone(), name.to_owned()))
+}
+
+/// Replaces a copy of a registered component by a `$ref` if it was not changed. The mark is
+/// removed from changed copies, which stay inline.
+fn use_component_ref<T: Component>(item: &mut RefOr<T>, registry: &Map<String, T>) {
+    let RefOr::Object(object) = item else {
+        return;
+    };
+    let Some(Value::String(name)) = object.extensions_mut().shift_remove(COMPONENT_EXTENSION)
+    else {
+        return;
+    };
+    if registry.get(&name) == Some(&*object) {
+        *item = RefOr::Ref(Ref {
+            reference: format!("#/components/{}/{name}", T::FIELD),
+        });
+    }
+}
+
+/// Uses `$ref`s for the registered response and its registered headers.
+fn use_response_refs(response: &mut RefOr<Response>, components: &ComponentRegistry) {
+    use_component_ref(response, &components.responses);
+    if let RefOr::Object(response) = response {
+        for header in response.headers.values_mut() {
+            use_component_ref(header, &components.headers);
+        }
+    }
+}
+
+/// Uses `$ref`s for the registered parameters, request body, responses and headers of `op`.
+fn use_component_refs(op: &mut Operation, components: &ComponentRegistry) {
+    for parameter in &mut op.parameters {
+        use_component_ref(parameter, &components.parameters);
+    }
+    if let Some(request_body) = &mut op.request_body {
+        use_component_ref(request_body, &components.request_bodies);
+    }
+    for response in op.responses.responses.values_mut() {
+        use_response_refs(response, components);
+    }
+}
+
+/// A catcher added with [`OpenApiGenerator::add_catcher`].
+#[derive(Debug, Clone)]
+struct Catcher {
+    name: String,
+    module_path: String,
+    status: Option<u16>,
+    response: Response,
+}
+
+/// The responses of the catchers, with the status code they handle, by the name of the
+/// component. Catchers are named after their function, or after their module qualified path
+/// (with `::` replaced by `_`) when several catchers have the same function name.
+fn name_catchers(catchers: Vec<Catcher>) -> Map<String, (Option<u16>, Response)> {
+    let mut names = HashMap::new();
+    for catcher in &catchers {
+        *names.entry(catcher.name.clone()).or_insert(0) += 1;
+    }
+    catchers
+        .into_iter()
+        .map(|catcher| {
+            let name = if names[&catcher.name] > 1 {
+                format!(
+                    "{}_{}",
+                    catcher.module_path.replace("::", "_"),
+                    catcher.name
+                )
+            } else {
+                catcher.name
+            };
+            (name, (catcher.status, catcher.response))
+        })
+        .collect()
+}
+
+/// Uses the responses of the catchers for the error responses of `op` that have no `content`,
+/// because their body is sent by the catcher. Responses with `content` are sent by the route
+/// itself and are kept.
+///
+/// A response without headers and without a description of its own, one that differs from the
+/// description of the catcher, is replaced by a reference to the catcher for its status code, or
+/// else to the default catcher. Other responses keep their description and headers and get the
+/// `content` of the catcher.
+fn add_catcher_responses(
+    op: &mut Operation,
+    catchers: &Map<String, (Option<u16>, Response)>,
+    catcher_responses: CatcherResponses,
+) {
+    if catchers.is_empty() {
+        return;
+    }
+    let reference = |name: &str| {
+        RefOr::Ref(Ref {
+            reference: format!("#/components/responses/{name}"),
+        })
+    };
+    let default_catcher = catchers
+        .iter()
+        .find_map(|(name, (status, _))| status.is_none().then_some(name));
+    if catcher_responses == CatcherResponses::AllOperations {
+        for (name, (status, _)) in catchers {
+            let status = match status {
+                Some(status) => status.to_string(),
+                None => "default".to_owned(),
+            };
+            op.responses
+                .responses
+                .entry(status)
+                .or_insert_with(|| reference(name));
+        }
+    }
+    for (status, item) in op.responses.responses.iter_mut() {
+        if !(status.starts_with('4') || status.starts_with('5')) {
+            continue;
+        }
+        let RefOr::Object(response) = item else {
+            continue;
+        };
+        if !response.content.is_empty() {
+            continue;
+        }
+        let catcher = catchers
+            .iter()
+            .find(|(_, (catcher_status, _))| {
+                catcher_status.is_some_and(|s| s.to_string() == *status)
+            })
+            .or_else(|| default_catcher.and_then(|name| catchers.get_key_value(name)));
+        let Some((name, (_, catcher))) = catcher else {
+            continue;
+        };
+        let has_own_description = !(response.description.is_empty()
+            || response.description == default_description(status)
+            || response.description == catcher.description);
+        if has_own_description || !response.headers.is_empty() {
+            response.content.clone_from(&catcher.content);
+        } else {
+            *item = reference(name);
+        }
+    }
+}
+
+/// Merges the operation of a route with a higher rank into the operation of `existing`, so it
+/// documents both routes.
+fn merge_ranked_operations(existing: &mut Operation, op: Operation) -> Result<()> {
+    // -- Parameters --
+    // A parameter is only required when all routes require it.
+    for param in &mut existing.parameters {
+        if let RefOr::Object(param) = param {
+            let in_op = op.parameters.iter().any(|other| match other {
+                RefOr::Object(other) => is_same_parameter(param, other),
+                RefOr::Ref(_) => false,
+            });
+            if !in_op && param.location != "path" {
+                param.required = false;
+            }
+        }
+    }
+    for param in op.parameters {
+        let mut param = match param {
+            RefOr::Object(param) => param,
+            RefOr::Ref(_) => {
+                if !existing.parameters.contains(&param) {
+                    existing.parameters.push(param);
+                }
+                continue;
+            }
+        };
+        let existing_param = existing.parameters.iter_mut().find_map(|p| match p {
+            RefOr::Object(p) if is_same_parameter(p, &param) => Some(p),
+            _ => None,
+        });
+        match existing_param {
+            Some(existing_param) => {
+                existing_param.required &= param.required;
+                if let (
+                    ParameterValue::Schema { schema, .. },
+                    ParameterValue::Schema { schema: other, .. },
+                ) = (&mut existing_param.value, param.value)
+                {
+                    one_of(schema, other);
+                }
+            }
+            None => {
+                if param.location != "path" {
+                    param.required = false;
+                }
+                existing.parameters.push(param.into());
+            }
+        }
+    }
+
+    // -- Request body --
+    existing.request_body = match (existing.request_body.take(), op.request_body) {
+        (Some(RefOr::Object(mut body)), Some(RefOr::Object(other))) => {
+            body.required &= other.required;
+            merge_content(&mut body.content, other.content);
+            Some(body.into())
+        }
+        // A request body that is only used by some of the routes is optional.
+        (Some(RefOr::Object(mut body)), None) | (None, Some(RefOr::Object(mut body))) => {
+            body.required = false;
+            Some(body.into())
+        }
+        (body, None) | (None, body) => body,
+        (Some(body), Some(other)) if body == other => Some(body),
+        (Some(_), Some(_)) => {
+            return Err(OpenApiError::new(
+                "Merging Ref request bodies is not supported.".to_owned(),
+            ))
+        }
+    };
+
+    // -- Responses --
+    for (status, response) in op.responses.responses {
+        match (existing.responses.responses.entry(status), response) {
+            (MapEntry::Vacant(e), response) => {
+                e.insert(response);
+            }
+            (MapEntry::Occupied(mut e), RefOr::Object(response)) => match e.get_mut() {
+                RefOr::Object(existing_response) => {
+                    if existing_response.description.is_empty() {
+                        existing_response.description = response.description;
+                    } else if !response.description.is_empty()
+                        && existing_response.description != response.description
+                    {
+                        existing_response.description.push('\n');
+                        existing_response
+                            .description
+                            .push_str(&response.description);
+                    }
+                    for (name, header) in response.headers {
+                        existing_response.headers.entry(name).or_insert(header);
+                    }
+                    merge_content(&mut existing_response.content, response.content);
+                }
+                RefOr::Ref(_) => {
+                    return Err(OpenApiError::new(
+                        "Merging Ref responses is not supported.".to_owned(),
+                    ))
+                }
+            },
+            (MapEntry::Occupied(e), response) => {
+                if *e.get() != response {
+                    return Err(OpenApiError::new(
+                        "Merging Ref responses is not supported.".to_owned(),
+                    ));
+                }
+            }
+        }
+    }
+
+    // -- Other fields --
+    // The first route determines the summary, description and operation id.
+    if existing.summary.is_none() {
+        existing.summary = op.summary;
+    }
+    if existing.description.is_none() {
+        existing.description = op.description;
+    }
+    for tag in op.tags {
+        if !existing.tags.contains(&tag) {
+            existing.tags.push(tag);
+        }
+    }
+    existing.deprecated &= op.deprecated;
+    // Any of the security requirements of both routes gives access. A route without security
+    // requirements makes security optional, which is represented by an empty requirement.
+    existing.security = match (existing.security.take(), op.security) {
+        (None, None) => None,
+        (security, other) => {
+            let mut merged = Vec::new();
+            for requirement in
+                [security, other]
+                    .into_iter()
+                    .flat_map(|requirements| match requirements {
+                        Some(requirements) if !requirements.is_empty() => requirements,
+                        _ => vec![Map::new()],
+                    })
+            {
+                if !merged.contains(&requirement) {
+                    merged.push(requirement);
+                }
+            }
+            Some(merged)
+        }
+    };
+    Ok(())
+}
+
+fn is_same_parameter(p1: &Parameter, p2: &Parameter) -> bool {
+    p1.name == p2.name && p1.location == p2.location
+}
+
+/// Adds the media types of `other` to `content`, combining the schemas of media types that are
+/// in both using `oneOf`.
+fn merge_content(content: &mut Map<String, MediaType>, other: Map<String, MediaType>) {
+    for (content_type, media) in other {
+        match content.entry(content_type) {
+            MapEntry::Vacant(e) => {
+                e.insert(media);
+            }
+            MapEntry::Occupied(mut e) => {
+                let existing = e.get_mut();
+                match (&mut existing.schema, media.schema) {
+                    (Some(schema), Some(other)) => one_of(schema, other),
+                    (schema @ None, other) => *schema = other,
+                    (Some(_), None) => {}
+                }
+                if existing.example.is_none() {
+                    existing.example = media.example;
+                }
+            }
+        }
+    }
+}
+
+/// Changes `schema` so it accepts `schema` or `other`, using `oneOf` when they differ.
+fn one_of(schema: &mut SchemaObject, other: SchemaObject) {
+    if *schema == other {
+        return;
+    }
+    let is_one_of = schema.as_object().is_some_and(|object| {
+        object.len() == 1 && object.get("oneOf").is_some_and(|list| list.is_array())
+    });
+    if is_one_of {
+        if let Some(serde_json::Value::Array(list)) = schema.get_mut("oneOf") {
+            let other = serde_json::Value::from(other);
+            if !list.contains(&other) {
+                list.push(other);
+            }
+        }
+        return;
+    }
+    let mut combined = SchemaObject::default();
+    combined.insert(
+        "oneOf".to_owned(),
+        serde_json::Value::Array(vec![
+            std::mem::take(schema).into(),
+            serde_json::Value::from(other),
+        ]),
+    );
+    *schema = combined;
+}
+
+/// Merges the content of `op` into `existing` if both operations document different media types,
+/// which is the case for routes with the same path and method but a different `format`.
+/// Returns `false` if nothing was merged.
+fn merge_format_variants(existing: &mut Operation, op: &Operation) -> bool {
+    let existing_types = media_types(existing);
+    let op_types = media_types(op);
+    if existing_types.is_empty()
+        || op_types.is_empty()
+        || existing_types.iter().any(|t| op_types.contains(t))
+    {
+        return false;
+    }
+    let request_body = match (&existing.request_body, &op.request_body) {
+        (Some(RefOr::Object(body)), Some(RefOr::Object(other))) => {
+            let mut body = body.clone();
+            extend_content(&mut body.content, &other.content);
+            Some(body.into())
+        }
+        (body, None) => body.clone(),
+        (None, other) => other.clone(),
+        // Referenced request bodies can not be merged.
+        _ => return false,
+    };
+    let mut responses = existing.responses.clone();
+    for (status, response) in &op.responses.responses {
+        match (responses.responses.get_mut(status), response) {
+            (None, _) => {
+                responses.responses.insert(status.clone(), response.clone());
+            }
+            (Some(RefOr::Object(existing)), RefOr::Object(response)) => {
+                extend_content(&mut existing.content, &response.content);
+            }
+            // Referenced responses can not be merged.
+            _ => return false,
+        }
+    }
+    existing.request_body = request_body;
+    existing.responses = responses;
+    true
+}
+
+/// Adds the media types of `other` that are not yet in `content`.
+fn extend_content(content: &mut Map<String, MediaType>, other: &Map<String, MediaType>) {
+    for (content_type, media) in other {
+        content
+            .entry(content_type.clone())
+            .or_insert_with(|| media.clone());
+    }
+}
+
+/// The media types the `format` of a route applies to: those of the request body when there is
+/// one, otherwise those of the successful responses.
+fn media_types(op: &Operation) -> Vec<String> {
+    if let Some(RefOr::Object(body)) = &op.request_body {
+        return body.content.keys().cloned().collect();
+    }
+    let mut types = Vec::new();
+    for (status, response) in &op.responses.responses {
+        if let RefOr::Object(response) = response {
+            if status.starts_with('2') || status == "default" {
+                types.extend(response.content.keys().cloned());
+            }
+        }
+    }
+    types
+}
+
+/// The schema settings of `settings` for its `openapi_version`. OpenAPI 3.1 and later use JSON
+/// Schema 2020-12, so the transforms that restrict schemas to the subset of JSON Schema supported
+/// by OpenAPI 3.0 are removed.
+fn schema_settings(settings: &OpenApiSettings) -> SchemaSettings {
+    let mut schema_settings = settings.schema_settings.clone();
+    schema_settings.transforms.push(Box::new(FileSchemas {
+        binary_schema: binary_schema(&settings.openapi_version),
+    }));
+    if settings.openapi_version.starts_with("3.0") {
+        return schema_settings;
+    }
+    if schema_settings.meta_schema.as_deref() == Some(meta_schemas::OPENAPI3) {
+        schema_settings.meta_schema = Some(meta_schemas::DRAFT2020_12.into());
+    }
+    schema_settings
+        .transforms
+        .retain(|transform| !is_openapi3_transform(transform.as_ref()));
+    schema_settings
+}
+
+/// See [`OpenApiGenerator::binary_schema`].
+fn binary_schema(openapi_version: &str) -> SchemaObject {
+    if openapi_version.starts_with("3.0") {
+        schemars::json_schema!({ "type": "string", "format": "binary" })
+    } else {
+        schemars::json_schema!({
+            "type": "string",
+            "contentMediaType": "application/octet-stream"
+        })
+    }
+}
+
+fn is_openapi3_transform(transform: &dyn GenTransform) -> bool {
+    transform.is::<ReplaceUnevaluatedProperties>()
+        || transform.is::<ReplaceBoolSchemas>()
+        || transform.is::<AddNullable>()
+        || transform.is::<RemoveRefSiblings>()
+        || transform.is::<SetSingleExample>()
+        || transform.is::<ReplaceConstValue>()
+        || transform.is::<ReplacePrefixItems>()
 }
 
 fn set_operation(path_item: &mut PathItem, method: Method, op: Operation) {
@@ -131,6 +1328,6 @@ fn set_operation(path_item: &mut PathItem, method: Method, op: Operation) {
         // Connect not available in OpenAPI3. Maybe should set in extensions?
         Connect => return,
     };
-    assert!(option.is_none());
-    option.replace(op);
+    // Operations are stored per path and method, so there is at most one operation to set.
+    *option = Some(op);
 }
@@ -63,6 +63,8 @@
 //! - `openapi_get_routes![...]`: To generate and add the `openapi.json` route.
 //! - `openapi_get_routes_spec![...]`: To generate and return a list of routes and the openapi spec.
 //! - `openapi_get_spec![...]`: To generate and return the openapi spec.
+//! - `asyncapi_get_spec![...]`: To generate and return the AsyncAPI spec of WebSocket and
+//!   Server-Sent Events routes.
 //!
 //! The last 3 macros have very similar behavior, but differ in what they return.
 //! Here is a list of the macros and what they return:
@@ -76,6 +78,8 @@
 
 mod error;
 
+/// Contains the trait `OpenApiExample`, which adds examples of a type to the documentation.
+pub mod example;
 /// Contains the `Generator` struct, which you can use to manually control the way a struct is
 /// represented in the documentation.
 pub mod gen;
@@ -114,6 +118,54 @@ pub struct OperationInfo {
     pub method: rocket::http::Method,
     /// Contains information to be showed in the documentation about this endpoint.
     pub operation: okapi::openapi3::Operation,
+    /// The `format` of the route, if it declares one. Routes with the same path and method that
+    /// all declare a different `format` are documented as one operation.
+    pub format: Option<String>,
+}
+
+/// Contains information about a Rocket catcher, see
+/// [`OpenApiSettings::catchers`](settings::OpenApiSettings::catchers).
+pub struct CatcherInfo {
+    /// The name of the catcher function, used as name in `components.responses`.
+    pub name: String,
+    /// The module path of the catcher function, like `my_crate::errors`. It is added to the name
+    /// in `components.responses` when catchers in different modules have the same name.
+    pub module_path: String,
+    /// The status code the catcher handles, `None` for a default catcher.
+    pub status: Option<u16>,
+    /// A description of the response, taken from the doc comments of the catcher.
+    pub description: Option<String>,
+    /// The responses of the return type of the catcher. They are combined into one response.
+    pub responses: okapi::openapi3::Responses,
+}
+
+/// The transport used by a message-driven endpoint documented in the AsyncAPI document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChannelProtocol {
+    /// A WebSocket connection, for example a route returning `rocket_ws::Channel`.
+    WebSocket,
+    /// A stream of Server-Sent Events, for example a route returning `EventStream`.
+    EventStream,
+}
+
+/// Contains information about a message-driven endpoint (WebSocket or Server-Sent Events).
+pub struct ChannelInfo {
+    /// The path of the endpoint.
+    pub path: String,
+    /// The transport used by this endpoint.
+    pub protocol: ChannelProtocol,
+    /// The `operationId` of the matching OpenAPI operation.
+    pub operation_id: Option<String>,
+    /// A short summary of what the endpoint does.
+    pub summary: Option<String>,
+    /// A verbose explanation of the endpoint.
+    pub description: Option<String>,
+    /// The parameters of the endpoint, only path parameters are used in the channel.
+    pub parameters: Vec<okapi::openapi3::RefOr<okapi::openapi3::Parameter>>,
+    /// Schema of the messages the server sends to the client.
+    pub send: Option<okapi::openapi3::SchemaObject>,
+    /// Schema of the messages the server receives from the client.
+    pub receive: Option<okapi::openapi3::SchemaObject>,
 }
 
 /// Convert OpenApi object to routable endpoint.
@@ -126,6 +178,16 @@ pub fn get_openapi_route(
     handlers::OpenApiHandler::new(spec).into_route(&settings.json_path)
 }
 
+/// Convert AsyncApi object to routable endpoint.
+///
+/// Used to serve an `AsyncApi` object as an `asyncapi.json` file in Rocket.
+pub fn get_asyncapi_route(
+    spec: okapi::asyncapi::AsyncApi,
+    settings: &settings::OpenApiSettings,
+) -> rocket::Route {
+    handlers::ContentHandler::json(&spec).into_route(&settings.asyncapi_json_path)
+}
+
 /// Mount endpoints and mount merged OpenAPI documentation.
 ///
 /// This macro just makes to code look cleaner and improves readability
@@ -253,6 +315,8 @@ macro_rules! get_nested_endpoints_and_docs {
 /// The key differences are that this macro will add an additional element to the
 /// resulting `Vec<rocket::Route>`, which serves a static file called
 /// `openapi.json`. This file can then be used to display the routes in the Swagger/RapiDoc UI.
+/// When any of the routes declares WebSocket or Server-Sent Events messages (see `send` and
+/// `receive` in `#[openapi]`), an `asyncapi.json` route is added as well.
 ///
 /// Example:
 /// ```rust,ignore
@@ -272,8 +336,11 @@ macro_rules! openapi_get_routes {
     // With settings
     ($settings:ident :
      $($route:expr),* $(,)*) => {{
-        let spec = rocket_okapi::openapi_spec![$($route),*](&$settings);
-        let routes = rocket_okapi::openapi_routes![$($route),*](Some(spec), &$settings);
+        let (spec, asyncapi) = rocket_okapi::openapi_and_asyncapi_spec![$($route),*](&$settings);
+        let mut routes = rocket_okapi::openapi_routes![$($route),*](Some(spec), &$settings);
+        if let Some(asyncapi) = asyncapi {
+            routes.push(rocket_okapi::get_asyncapi_route(asyncapi, &$settings));
+        }
         routes
     }};
 
@@ -354,3 +421,35 @@ macro_rules! openapi_get_spec {
         rocket_okapi::openapi_get_spec![settings: $($route),*]
     }};
 }
+
+/// Generate `AsyncApi` spec only, does not generate routes.
+/// The document contains a channel for every route that declared the messages it sends or
+/// receives using `#[openapi(send = "...", receive = "...")]`. Schemas are shared with the
+/// `OpenApi` spec, so both documents use the same component names.
+///
+/// Example:
+/// ```rust,ignore
+/// use okapi::asyncapi::AsyncApi;
+/// let settings = rocket_okapi::settings::OpenApiSettings::new();
+/// let spec: AsyncApi = asyncapi_get_spec![settings: echo_channel, ticker_events];
+/// ```
+/// Or
+/// ```rust,ignore
+/// use okapi::asyncapi::AsyncApi;
+/// let spec: AsyncApi = asyncapi_get_spec![echo_channel, ticker_events];
+/// ```
+#[macro_export]
+macro_rules! asyncapi_get_spec {
+    // With settings
+    ($settings:ident :
+     $($route:expr),* $(,)*) => {{
+        let spec = rocket_okapi::asyncapi_spec![$($route),*](&$settings);
+        spec
+    }};
+
+    // Without settings
+    ($($route:expr),* $(,)*) => {{
+        let settings = rocket_okapi::settings::OpenApiSettings::new();
+        rocket_okapi::asyncapi_get_spec![settings: $($route),*]
+    }};
+}
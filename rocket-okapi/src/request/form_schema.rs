@@ -0,0 +1,236 @@
+//! Functions used by `#[derive(FormSchema)]` to create the schema of a form from Rocket's
+//! `#[field(...)]` attributes. They can also be used when implementing `JsonSchema` manually.
+
+use okapi::openapi3::SchemaObject;
+use rocket::form::{FromForm, Options};
+use rocket::http::ContentType;
+use schemars::transform::{transform_subschemas, Transform};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::ops::{Bound, RangeBounds};
+
+/// Returns `true` if Rocket requires a value for a form field of type `T`, so if it has no
+/// default value. `Option<T>` and `Vec<T>` for example are not required.
+/// Forms and query strings are parsed leniently.
+#[must_use]
+pub fn form_field_is_required<'v, T: FromForm<'v>>() -> bool {
+    T::default(Options::Lenient).is_none()
+}
+
+/// Create the schema of a form with the given fields: their name, schema and if they are
+/// required.
+#[must_use]
+pub fn form_struct_schema(
+    description: Option<&str>,
+    fields: Vec<(String, SchemaObject, bool)>,
+) -> SchemaObject {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    for (name, schema, is_required) in fields {
+        if is_required {
+            required.push(Value::String(name.clone()));
+        }
+        properties.insert(name, schema.to_value());
+    }
+    let mut schema = Map::new();
+    schema.insert("type".to_owned(), "object".into());
+    if let Some(description) = description {
+        schema.insert("description".to_owned(), description.into());
+    }
+    schema.insert("properties".to_owned(), properties.into());
+    if !required.is_empty() {
+        schema.insert("required".to_owned(), required.into());
+    }
+    schema.into()
+}
+
+/// The extension that marks the schema of an uploaded file. It is replaced by the schema of
+/// binary data for the OpenAPI version when the specification is generated.
+const FILE_EXTENSION: &str = "x-okapi-file";
+
+/// The schema of an uploaded file, like `TempFile`: a binary string.
+///
+/// The schema is marked as a file. When the specification is generated it becomes the
+/// [`OpenApiGenerator::binary_schema`](crate::gen::OpenApiGenerator::binary_schema) of the
+/// OpenAPI version: a string with `format: binary` for OpenAPI 3.0, or with `contentMediaType`
+/// for OpenAPI 3.1.
+#[must_use]
+pub fn file_schema() -> SchemaObject {
+    let mut schema = Map::new();
+    schema.insert("type".to_owned(), "string".into());
+    schema.insert("format".to_owned(), "binary".into());
+    schema.insert(FILE_EXTENSION.to_owned(), true.into());
+    schema.into()
+}
+
+/// Replaces the schemas marked by [`file_schema`] by the schema of binary data of the OpenAPI
+/// version, and removes the mark. Added to the transforms of the schema generator by
+/// `OpenApiGenerator`.
+#[derive(Debug, Clone)]
+pub(crate) struct FileSchemas {
+    /// The schema of binary data, see `OpenApiGenerator::binary_schema`.
+    pub binary_schema: SchemaObject,
+}
+
+impl Transform for FileSchemas {
+    fn transform(&mut self, schema: &mut SchemaObject) {
+        if let Some(schema) = schema.as_object_mut() {
+            if schema.remove(FILE_EXTENSION).is_some() {
+                schema.remove("format");
+                for (key, value) in self.binary_schema.as_object().into_iter().flatten() {
+                    // Keep the content type of the `ext(content_type)` validator.
+                    schema.entry(key.clone()).or_insert_with(|| value.clone());
+                }
+            }
+        }
+        transform_subschemas(self, schema);
+    }
+}
+
+/// The schema of multiple uploaded files with the same name, like `Vec<TempFile>`.
+#[must_use]
+pub fn file_array_schema() -> SchemaObject {
+    let mut schema = Map::new();
+    schema.insert("type".to_owned(), "array".into());
+    schema.insert("items".to_owned(), file_schema().to_value());
+    schema.into()
+}
+
+/// Document the `ext(content_type)` validator of a file with `contentMediaType`, which is used
+/// as the `Encoding` of the field in `multipart/form-data` request bodies.
+pub fn set_ext_validation(schema: &mut SchemaObject, content_type: &ContentType) {
+    let schema = if schema.get("items").is_some_and(Value::is_object) {
+        schema.get_mut("items").and_then(Value::as_object_mut)
+    } else {
+        schema.as_object_mut()
+    };
+    let Some(schema) = schema else {
+        return;
+    };
+    schema.insert(
+        "contentMediaType".to_owned(),
+        content_type.to_string().into(),
+    );
+}
+
+/// The content types of the file fields of a form schema (without references), by field name.
+/// Files without a known content type are `application/octet-stream`.
+#[must_use]
+pub fn file_field_content_types(schema: &SchemaObject) -> Vec<(String, String)> {
+    let Some(Value::Object(properties)) = schema.get("properties") else {
+        return Vec::new();
+    };
+    properties
+        .iter()
+        .filter_map(|(name, property)| {
+            let file = match property.get("items") {
+                Some(items) if property.get("type") == Some(&Value::from("array")) => items,
+                _ => property,
+            };
+            file.get(FILE_EXTENSION)?;
+            let content_type = file
+                .get("contentMediaType")
+                .and_then(Value::as_str)
+                .unwrap_or("application/octet-stream");
+            Some((name.clone(), content_type.to_owned()))
+        })
+        .collect()
+}
+
+/// Document the `len(range)` validator: `minLength`/`maxLength` for strings, `minItems`/`maxItems`
+/// for sequences and `minProperties`/`maxProperties` for maps.
+/// Note that Rocket counts the bytes of strings, not the characters.
+pub fn set_len_validation<L, R>(schema: &mut SchemaObject, range: R)
+where
+    L: Copy + TryInto<u64>,
+    R: RangeBounds<L>,
+{
+    let (min_key, max_key) = if has_type(schema, "array") {
+        ("minItems", "maxItems")
+    } else if has_type(schema, "object") {
+        ("minProperties", "maxProperties")
+    } else if has_type(schema, "string") {
+        ("minLength", "maxLength")
+    } else {
+        return;
+    };
+    let to_u64 = |value: &L| (*value).try_into().ok();
+    let min = match range.start_bound() {
+        Bound::Included(start) => to_u64(start),
+        Bound::Excluded(start) => to_u64(start).map(|start| start + 1),
+        Bound::Unbounded => None,
+    };
+    let max = match range.end_bound() {
+        Bound::Included(end) => to_u64(end),
+        Bound::Excluded(end) => to_u64(end).and_then(|end| end.checked_sub(1)),
+        Bound::Unbounded => None,
+    };
+    if let Some(min) = min.filter(|min| *min > 0) {
+        schema.insert(min_key.to_owned(), min.into());
+    }
+    if let Some(max) = max {
+        schema.insert(max_key.to_owned(), max.into());
+    }
+}
+
+/// Document the `range(range)` validator with `minimum` and `maximum`.
+pub fn set_range_validation<R: RangeBounds<isize>>(schema: &mut SchemaObject, range: R) {
+    if !has_type(schema, "integer") && !has_type(schema, "number") {
+        return;
+    }
+    match range.start_bound() {
+        Bound::Included(start) => {
+            schema.insert("minimum".to_owned(), (*start).into());
+        }
+        Bound::Excluded(start) => {
+            schema.insert("minimum".to_owned(), (start + 1).into());
+        }
+        Bound::Unbounded => {}
+    }
+    match range.end_bound() {
+        Bound::Included(end) => {
+            schema.insert("maximum".to_owned(), (*end).into());
+        }
+        Bound::Excluded(end) => {
+            schema.insert("maximum".to_owned(), (end - 1).into());
+        }
+        Bound::Unbounded => {}
+    }
+}
+
+/// Document the `one_of(items)` and `contains(item)` validators of sequences, which require that
+/// one of the items is present, as `contains: { enum: [...] }`.
+///
+/// For other types Rocket checks if the value contains one of the items (like a character in a
+/// string), which can not be documented.
+pub fn set_contains_validation<I>(schema: &mut SchemaObject, items: I)
+where
+    I: IntoIterator,
+    I::Item: Serialize,
+{
+    if !has_type(schema, "array") {
+        return;
+    }
+    let items: Vec<Value> = items
+        .into_iter()
+        .filter_map(|item| serde_json::to_value(item).ok())
+        .collect();
+    let mut contains = Map::new();
+    contains.insert("enum".to_owned(), items.into());
+    schema.insert("contains".to_owned(), contains.into());
+}
+
+/// Document the `eq(value)` validator as an `enum` with a single value.
+pub fn set_eq_validation<T: Serialize>(schema: &mut SchemaObject, value: T) {
+    if let Ok(value) = serde_json::to_value(value) {
+        schema.insert("enum".to_owned(), vec![value].into());
+    }
+}
+
+fn has_type(schema: &SchemaObject, ty: &str) -> bool {
+    match schema.get("type") {
+        Some(Value::String(t)) => t == ty,
+        Some(Value::Array(types)) => types.iter().any(|t| t == ty),
+        _ => false,
+    }
+}
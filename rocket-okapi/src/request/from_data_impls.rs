@@ -1,7 +1,7 @@
 use super::OpenApiFromData;
 use crate::gen::OpenApiGenerator;
 use okapi::{
-    openapi3::{MediaType, RequestBody},
+    openapi3::{Encoding, MediaType, RefOr, RequestBody, Response, Responses},
     Map,
 };
 use rocket::data::Data;
@@ -31,6 +31,132 @@ macro_rules! fn_request_body {
             ..okapi::openapi3::RequestBody::default()
         })
     }};
+    ($gen:ident, $ty:path, $mime_type:expr, $limit:expr) => {{
+        let request_body: Result = fn_request_body!($gen, $ty, $mime_type);
+        request_body.map(|request_body| with_body_limit($gen, request_body, $limit))
+    }};
+}
+
+/// The request body of binary data, like a file.
+fn binary_request_body(gen: &OpenApiGenerator, limit: Option<&str>) -> Result {
+    let mut content = Map::new();
+    content.insert(
+        "application/octet-stream".to_owned(),
+        MediaType {
+            schema: Some(gen.binary_schema()),
+            ..MediaType::default()
+        },
+    );
+    let request_body = RequestBody {
+        content,
+        required: true,
+        ..RequestBody::default()
+    };
+    Ok(match limit {
+        Some(limit) => with_body_limit(gen, request_body, limit),
+        None => request_body,
+    })
+}
+
+/// Document the configured size limit with the name `limit` (see [`rocket::data::Limits`]) in
+/// the `x-max-body-size` extension, and as the `maxLength` of string schemas.
+fn with_body_limit(
+    gen: &OpenApiGenerator,
+    mut request_body: RequestBody,
+    limit: &str,
+) -> RequestBody {
+    let Some(limit) = gen.body_limit(limit) else {
+        return request_body;
+    };
+    for media_type in request_body.content.values_mut() {
+        if let Some(schema) = media_type.schema.as_mut() {
+            if schema.get("type").and_then(|t| t.as_str()) == Some("string") {
+                schema.insert("maxLength".to_owned(), limit.as_u64().into());
+            }
+        }
+    }
+    request_body
+        .extensions
+        .insert("x-max-body-size".to_owned(), limit.as_u64().into());
+    request_body
+}
+
+/// Responses of data guards that fail with `400 Bad Request` when the body could not be read,
+/// which includes a body that exceeds the configured limit.
+const READ_ERRORS: &[(u16, &str)] = &[(
+    400,
+    "The request body could not be read or exceeds the size limit.",
+)];
+
+/// Responses of `Capped<T>` data guards, which only fail when the body could not be read.
+const IO_ERRORS: &[(u16, &str)] = &[(400, "The request body could not be read.")];
+
+/// Responses of data guards that deserialize the body, like `Json<T>`.
+const PARSE_ERRORS: &[(u16, &str)] = &[
+    (400, "The request body could not be read."),
+    (413, "The request body exceeds the size limit."),
+    (
+        422,
+        "The request body could not be parsed or does not match the schema.",
+    ),
+];
+
+fn error_responses(errors: &[(u16, &str)]) -> crate::Result<Responses> {
+    let mut responses = Responses::default();
+    for (status, description) in errors {
+        responses.responses.insert(
+            status.to_string(),
+            RefOr::Object(Response {
+                description: (*description).to_owned(),
+                ..Response::default()
+            }),
+        );
+    }
+    Ok(responses)
+}
+
+/// Like `error_responses`, but the response with the `status` that Rocket uses when the body
+/// exceeds the size limit with the name `limit` mentions the configured limit.
+fn limit_error_responses(
+    gen: &OpenApiGenerator,
+    errors: &[(u16, &str)],
+    status: u16,
+    limit: &str,
+) -> crate::Result<Responses> {
+    limits_error_responses(gen, errors, status, &[limit])
+}
+
+/// Like [`limit_error_responses`], for data guards that use a different limit depending on the
+/// content type of the request, like `Form<T>`.
+fn limits_error_responses(
+    gen: &OpenApiGenerator,
+    errors: &[(u16, &str)],
+    status: u16,
+    limits: &[&str],
+) -> crate::Result<Responses> {
+    let mut responses = error_responses(errors)?;
+    if let Some(RefOr::Object(response)) = responses.responses.get_mut(&status.to_string()) {
+        for limit in limits {
+            if let Some(size) = gen.body_limit(limit) {
+                response.description =
+                    format!("{} The `{limit}` limit is {size}.", response.description);
+            }
+        }
+    }
+    Ok(responses)
+}
+
+/// The media types of a `Form<T>` with the limit Rocket uses for each of them. Files can only be
+/// uploaded using `multipart/form-data`.
+fn form_media_types(has_files: bool) -> &'static [(&'static str, &'static str)] {
+    if has_files {
+        &[("multipart/form-data", "data-form")]
+    } else {
+        &[
+            ("application/x-www-form-urlencoded", "form"),
+            ("multipart/form-data", "data-form"),
+        ]
+    }
 }
 
 // Implement `OpenApiFromData` for everything that implements `FromData`
@@ -41,7 +167,11 @@ macro_rules! fn_request_body {
 
 impl<'r> OpenApiFromData<'r> for &'r str {
     fn request_body(gen: &mut OpenApiGenerator) -> Result {
-        fn_request_body!(gen, str, "application/octet-stream")
+        fn_request_body!(gen, str, "application/octet-stream", "string")
+    }
+
+    fn get_responses(gen: &mut OpenApiGenerator) -> crate::Result<Responses> {
+        limit_error_responses(gen, READ_ERRORS, 400, "string")
     }
 }
 
@@ -49,12 +179,20 @@ impl<'r> OpenApiFromData<'r> for &'r [u8] {
     fn request_body(gen: &mut OpenApiGenerator) -> Result {
         Vec::<u8>::request_body(gen)
     }
+
+    fn get_responses(gen: &mut OpenApiGenerator) -> crate::Result<Responses> {
+        limit_error_responses(gen, READ_ERRORS, 400, "bytes")
+    }
 }
 
 impl<'r> OpenApiFromData<'r> for Cow<'r, str> {
     fn request_body(gen: &mut OpenApiGenerator) -> Result {
         <&'r str>::request_body(gen)
     }
+
+    fn get_responses(gen: &mut OpenApiGenerator) -> crate::Result<Responses> {
+        limit_error_responses(gen, READ_ERRORS, 400, "string")
+    }
 }
 
 // ## Implementors
@@ -62,26 +200,42 @@ impl<'r> OpenApiFromData<'r> for Cow<'r, str> {
 // See: https://github.com/GREsau/schemars/issues/103
 impl<'r> OpenApiFromData<'r> for &'r rocket::http::RawStr {
     fn request_body(gen: &mut OpenApiGenerator) -> Result {
-        Vec::<u8>::request_body(gen)
+        binary_request_body(gen, Some("string"))
+    }
+
+    fn get_responses(gen: &mut OpenApiGenerator) -> crate::Result<Responses> {
+        limit_error_responses(gen, READ_ERRORS, 400, "string")
     }
 }
 
 // Waiting for https://github.com/GREsau/schemars/issues/103
 impl<'r> OpenApiFromData<'r> for rocket::fs::TempFile<'_> {
     fn request_body(gen: &mut OpenApiGenerator) -> Result {
-        Vec::<u8>::request_body(gen)
+        binary_request_body(gen, Some("file"))
+    }
+
+    fn get_responses(gen: &mut OpenApiGenerator) -> crate::Result<Responses> {
+        limit_error_responses(gen, READ_ERRORS, 400, "file")
     }
 }
 
 impl<'r> OpenApiFromData<'r> for String {
     fn request_body(gen: &mut OpenApiGenerator) -> Result {
-        fn_request_body!(gen, String, "application/octet-stream")
+        fn_request_body!(gen, String, "application/octet-stream", "string")
+    }
+
+    fn get_responses(gen: &mut OpenApiGenerator) -> crate::Result<Responses> {
+        limit_error_responses(gen, READ_ERRORS, 400, "string")
     }
 }
 
 impl<'r> OpenApiFromData<'r> for Vec<u8> {
     fn request_body(gen: &mut OpenApiGenerator) -> Result {
-        fn_request_body!(gen, Vec<u8>, "application/octet-stream")
+        binary_request_body(gen, Some("bytes"))
+    }
+
+    fn get_responses(gen: &mut OpenApiGenerator) -> crate::Result<Responses> {
+        limit_error_responses(gen, READ_ERRORS, 400, "bytes")
     }
 }
 
@@ -89,6 +243,10 @@ impl<'r> OpenApiFromData<'r> for rocket::data::Capped<&'r str> {
     fn request_body(gen: &mut OpenApiGenerator) -> Result {
         <&'r str>::request_body(gen)
     }
+
+    fn get_responses(_gen: &mut OpenApiGenerator) -> crate::Result<Responses> {
+        error_responses(IO_ERRORS)
+    }
 }
 
 // See: https://github.com/GREsau/schemars/issues/103
@@ -96,54 +254,124 @@ impl<'r> OpenApiFromData<'r> for rocket::data::Capped<&'r rocket::http::RawStr>
     fn request_body(gen: &mut OpenApiGenerator) -> Result {
         <&'r rocket::http::RawStr>::request_body(gen)
     }
+
+    fn get_responses(_gen: &mut OpenApiGenerator) -> crate::Result<Responses> {
+        error_responses(IO_ERRORS)
+    }
 }
 
 impl<'r> OpenApiFromData<'r> for rocket::data::Capped<&'r [u8]> {
     fn request_body(gen: &mut OpenApiGenerator) -> Result {
         <&'r [u8]>::request_body(gen)
     }
+
+    fn get_responses(_gen: &mut OpenApiGenerator) -> crate::Result<Responses> {
+        error_responses(IO_ERRORS)
+    }
 }
 
 impl<'r> OpenApiFromData<'r> for rocket::data::Capped<rocket::fs::TempFile<'_>> {
     fn request_body(gen: &mut OpenApiGenerator) -> Result {
         rocket::fs::TempFile::request_body(gen)
     }
+
+    fn get_responses(_gen: &mut OpenApiGenerator) -> crate::Result<Responses> {
+        error_responses(IO_ERRORS)
+    }
 }
 
 impl<'r> OpenApiFromData<'r> for rocket::data::Capped<Cow<'_, str>> {
     fn request_body(gen: &mut OpenApiGenerator) -> Result {
         <Cow<'r, str>>::request_body(gen)
     }
+
+    fn get_responses(_gen: &mut OpenApiGenerator) -> crate::Result<Responses> {
+        error_responses(IO_ERRORS)
+    }
 }
 
 impl<'r> OpenApiFromData<'r> for rocket::data::Capped<String> {
     fn request_body(gen: &mut OpenApiGenerator) -> Result {
         String::request_body(gen)
     }
+
+    fn get_responses(_gen: &mut OpenApiGenerator) -> crate::Result<Responses> {
+        error_responses(IO_ERRORS)
+    }
 }
 
 impl<'r> OpenApiFromData<'r> for rocket::data::Capped<Vec<u8>> {
     fn request_body(gen: &mut OpenApiGenerator) -> Result {
         Vec::<u8>::request_body(gen)
     }
+
+    fn get_responses(_gen: &mut OpenApiGenerator) -> crate::Result<Responses> {
+        error_responses(IO_ERRORS)
+    }
 }
 
 impl<'r> OpenApiFromData<'r> for Data<'r> {
     fn request_body(gen: &mut OpenApiGenerator) -> Result {
-        Vec::<u8>::request_body(gen)
+        binary_request_body(gen, None)
     }
 }
 
 // `OpenApiFromForm` is correct, not a mistake, as Rocket requires `FromForm`.
 impl<'r, T: JsonSchema + super::OpenApiFromForm<'r>> OpenApiFromData<'r> for rocket::form::Form<T> {
     fn request_body(gen: &mut OpenApiGenerator) -> Result {
-        fn_request_body!(gen, T, "multipart/form-data")
+        let schema = gen.json_schema::<T>();
+        let files = super::form_schema::file_field_content_types(&gen.json_schema_no_ref::<T>());
+        let mut content = Map::new();
+        for (content_type, limit) in form_media_types(!files.is_empty()) {
+            let mut media_type = MediaType {
+                schema: Some(schema.clone()),
+                ..MediaType::default()
+            };
+            if *content_type == "multipart/form-data" {
+                media_type.encoding = files
+                    .iter()
+                    .map(|(name, content_type)| {
+                        let encoding = Encoding {
+                            content_type: Some(content_type.clone()),
+                            ..Encoding::default()
+                        };
+                        (name.clone(), encoding)
+                    })
+                    .collect();
+            }
+            // Rocket limits each content type differently, so the limit is documented per media
+            // type instead of for the whole request body.
+            if let Some(size) = gen.body_limit(limit) {
+                media_type
+                    .extensions
+                    .insert("x-max-body-size".to_owned(), size.as_u64().into());
+            }
+            content.insert((*content_type).to_owned(), media_type);
+        }
+        Ok(RequestBody {
+            content,
+            required: true,
+            ..RequestBody::default()
+        })
+    }
+
+    fn get_responses(gen: &mut OpenApiGenerator) -> crate::Result<Responses> {
+        let files = super::form_schema::file_field_content_types(&gen.json_schema_no_ref::<T>());
+        let limits: Vec<&str> = form_media_types(!files.is_empty())
+            .iter()
+            .map(|(_, limit)| *limit)
+            .collect();
+        limits_error_responses(gen, PARSE_ERRORS, 413, &limits)
     }
 }
 
 impl<'r, T: JsonSchema + Deserialize<'r>> OpenApiFromData<'r> for Json<T> {
     fn request_body(gen: &mut OpenApiGenerator) -> Result {
-        fn_request_body!(gen, T, "application/json")
+        fn_request_body!(gen, T, "application/json", "json")
+    }
+
+    fn get_responses(gen: &mut OpenApiGenerator) -> crate::Result<Responses> {
+        limit_error_responses(gen, PARSE_ERRORS, 413, "json")
     }
 }
 
@@ -152,7 +380,11 @@ impl<'r, T: JsonSchema + Deserialize<'r>> OpenApiFromData<'r>
     for rocket::serde::msgpack::MsgPack<T>
 {
     fn request_body(gen: &mut OpenApiGenerator) -> Result {
-        fn_request_body!(gen, T, "application/msgpack")
+        fn_request_body!(gen, T, "application/msgpack", "msgpack")
+    }
+
+    fn get_responses(gen: &mut OpenApiGenerator) -> crate::Result<Responses> {
+        limit_error_responses(gen, PARSE_ERRORS, 413, "msgpack")
     }
 }
 
@@ -1,6 +1,6 @@
 use crate::gen::OpenApiGenerator;
 use okapi::openapi3::SchemaObject;
-use okapi::openapi3::{Object, Parameter, ParameterValue};
+use okapi::openapi3::{Object, Parameter, ParameterStyle, ParameterValue};
 use schemars::JsonSchema;
 use schemars::Schema;
 use serde_json::Value;
@@ -33,26 +33,70 @@ where
         }
     }
     if !properties.is_empty() {
+        // Only the properties in the `required` list of the structure are required.
+        let required_properties: Vec<&str> = schema
+            .get("required")
+            .and_then(Value::as_array)
+            .map(|list| list.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
         for (key, property) in properties {
             let prop_schema: Schema = property.try_into().unwrap_or_default();
-            parameter_list.push(parameter_from_schema(prop_schema, key, required));
+            let prop_required = required && required_properties.contains(&key.as_str());
+            parameter_list.push(parameter_from_schema(gen, prop_schema, key, prop_required));
         }
     } else {
-        parameter_list.push(parameter_from_schema(schema, name, required));
+        parameter_list.push(parameter_from_schema(gen, schema, name, required));
     }
     // Nothing else to handle here
     parameter_list
 }
 
-fn parameter_from_schema(schema: SchemaObject, name: String, mut required: bool) -> Parameter {
-    // Check if parameter is optional (only is not already optional)
-    if required
-        && schema
-            .as_object()
-            .and_then(|o| o.get("nullable"))
-            .and_then(|v| v.as_bool())
-            == Some(true)
-    {
+/// Generate the `Parameter` of a named `?<name>` query segment, for example `?<filter>`.
+/// Unlike [`get_nested_form_parameters`] (used for `?<name..>`) the fields of a structure are not
+/// flattened, as Rocket expects them as `name.field` or `name[field]`. So structures and maps are
+/// documented as a single parameter with `style: deepObject`.
+pub fn get_named_form_parameter<T>(
+    gen: &mut OpenApiGenerator,
+    name: String,
+    required: bool,
+) -> Parameter
+where
+    T: JsonSchema,
+{
+    let schema = gen.json_schema::<T>();
+    parameter_from_schema(gen, schema, name, required)
+}
+
+/// Whether the schema accepts `null`: `nullable` in OpenAPI 3.0, or a `null` type in OpenAPI 3.1.
+fn accepts_null(schema: &SchemaObject) -> bool {
+    if schema.get("nullable").and_then(Value::as_bool) == Some(true) {
+        return true;
+    }
+    match schema.get("type") {
+        Some(Value::String(ty)) => ty == "null",
+        Some(Value::Array(types)) => types.iter().any(|ty| ty == "null"),
+        _ => ["anyOf", "oneOf"].iter().any(|key| {
+            schema
+                .get(*key)
+                .and_then(Value::as_array)
+                .is_some_and(|schemas| {
+                    schemas
+                        .iter()
+                        .any(|s| s.get("type") == Some(&"null".into()))
+                })
+        }),
+    }
+}
+
+fn parameter_from_schema(
+    gen: &OpenApiGenerator,
+    schema: SchemaObject,
+    name: String,
+    mut required: bool,
+) -> Parameter {
+    // Check if parameter is optional (only is not already optional).
+    // A value that is nullable or has a default value does not have to be given.
+    if required && (accepts_null(&schema) || schema.get("default").is_some()) {
         required = false;
     }
     let description = schema
@@ -60,6 +104,7 @@ fn parameter_from_schema(schema: SchemaObject, name: String, mut required: bool)
         .and_then(|o| o.get("description"))
         .and_then(|v| v.as_str())
         .map(|s| s.to_string());
+    let (style, explode) = query_style(gen, &schema);
     Parameter {
         name,
         location: "query".to_owned(),
@@ -68,8 +113,8 @@ fn parameter_from_schema(schema: SchemaObject, name: String, mut required: bool)
         deprecated: false,
         allow_empty_value: false,
         value: ParameterValue::Schema {
-            style: None,
-            explode: None,
+            style,
+            explode,
             allow_reserved: false,
             schema,
             example: None,
@@ -78,3 +123,35 @@ fn parameter_from_schema(schema: SchemaObject, name: String, mut required: bool)
         extensions: Object::default(),
     }
 }
+
+/// The `style` and `explode` matching the names Rocket's form parser accepts.
+/// Sequences are repeated (`tags=a&tags=b`) and the fields of structures and maps are named
+/// `name[field]` (`deepObject`), which Rocket treats the same as `name.field`.
+fn query_style(
+    gen: &OpenApiGenerator,
+    schema: &SchemaObject,
+) -> (Option<ParameterStyle>, Option<bool>) {
+    let schema = resolve_ref(gen, schema).unwrap_or(schema.as_value());
+    let is_type = |ty: &str| match schema.get("type") {
+        Some(Value::String(t)) => t == ty,
+        Some(Value::Array(types)) => types.iter().any(|t| t == ty),
+        _ => false,
+    };
+    if is_type("array") {
+        (Some(ParameterStyle::Form), Some(true))
+    } else if is_type("object")
+        || schema.get("properties").is_some()
+        || schema.get("additionalProperties").is_some()
+    {
+        (Some(ParameterStyle::DeepObject), Some(true))
+    } else {
+        (None, None)
+    }
+}
+
+/// Find the definition a `$ref` schema refers to.
+fn resolve_ref<'a>(gen: &'a OpenApiGenerator, schema: &SchemaObject) -> Option<&'a Value> {
+    let reference = schema.get("$ref")?.as_str()?;
+    let name = reference.rsplit('/').next()?;
+    gen.schema_generator().definitions().get(name)
+}
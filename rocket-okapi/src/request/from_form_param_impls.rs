@@ -1,9 +1,13 @@
 //! Implement `OpenApiFromForm` for everything that implements `FromForm`
 //! <https://docs.rs/rocket/latest/rocket/form/trait.FromForm.html#foreign-impls>
 
-use super::{get_nested_form_parameters, OpenApiFromForm, OpenApiFromFormField};
+use super::{
+    get_named_form_parameter, get_nested_form_parameters, OpenApiFromForm, OpenApiFromFormField,
+};
 use crate::gen::OpenApiGenerator;
 use okapi::openapi3::{Object, Parameter, ParameterValue};
+use rocket::form::error::ErrorKind;
+use rocket::form::Options;
 use schemars::JsonSchema;
 
 type Result = crate::Result<Parameter>;
@@ -17,7 +21,43 @@ where
         name: String,
         required: bool,
     ) -> crate::Result<Vec<Parameter>> {
-        Ok(get_nested_form_parameters::<T>(gen, name, required))
+        // Rocket uses the default value when the value is missing, so it is not required.
+        // This is the case for `Option<T>`, `Vec<T>` and structures where every field has a
+        // default. Query strings are always parsed leniently.
+        let required = required && T::default(Options::Lenient).is_none();
+        let mut parameters = get_nested_form_parameters::<T>(gen, name.clone(), required);
+        // The fields of a structure with a `#[field(default = ...)]` are not in the `required`
+        // list of its schema, so ask Rocket which fields it is missing in an empty form.
+        let missing = missing_fields::<T>();
+        for parameter in &mut parameters {
+            if parameter.name != name && !missing.contains(&parameter.name) {
+                parameter.required = false;
+            }
+        }
+        Ok(parameters)
+    }
+
+    fn form_named_parameter(
+        gen: &mut OpenApiGenerator,
+        name: String,
+        required: bool,
+    ) -> crate::Result<Vec<Parameter>> {
+        let required = required && T::default(Options::Lenient).is_none();
+        Ok(vec![get_named_form_parameter::<T>(gen, name, required)])
+    }
+}
+
+/// The names of the fields that Rocket reports as missing when parsing an empty form into `T`.
+/// Fields that have a default value, like `Option<T>` or a field with `#[field(default = ...)]`,
+/// are not missing.
+fn missing_fields<'r, T: rocket::form::FromForm<'r>>() -> Vec<String> {
+    match T::finalize(T::init(Options::Lenient)) {
+        Ok(_) => Vec::new(),
+        Err(errors) => errors
+            .iter()
+            .filter(|error| matches!(error.kind, ErrorKind::Missing))
+            .filter_map(|error| Some(error.name.as_ref()?.keys().next()?.as_str().to_owned()))
+            .collect(),
     }
 }
 
@@ -26,6 +66,7 @@ where
     T: rocket::form::FromFormField<'r> + JsonSchema,
 {
     fn form_parameter(gen: &mut OpenApiGenerator, name: String, required: bool) -> Result {
+        let required = required && <T as rocket::form::FromFormField>::default().is_none();
         let schema = gen.json_schema::<T>();
         Ok(Parameter {
             name,
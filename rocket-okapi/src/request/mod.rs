@@ -5,13 +5,15 @@ mod from_param_impls;
 mod from_request_impls;
 mod from_segments_impls;
 
+pub mod form_schema;
+
 use super::gen::OpenApiGenerator;
 use super::Result;
 use okapi::openapi3::{Parameter, RequestBody, Responses, SecurityRequirement, SecurityScheme};
 
 /// Expose this to the public to be use when manually implementing a
 /// [Form Guard](https://api.rocket.rs/master/rocket/form/trait.FromForm.html).
-pub use from_form_multi_param_impls::get_nested_form_parameters;
+pub use from_form_multi_param_impls::{get_named_form_parameter, get_nested_form_parameters};
 
 /// This trait is used to document the request body that implements
 /// [`FromData`](rocket::data::FromData).
@@ -19,6 +21,13 @@ pub trait OpenApiFromData<'r>: rocket::data::FromData<'r> {
     /// Return a [`RequestBody`] containing the information required to document the
     /// [`FromData`](rocket::data::FromData) object.
     fn request_body(gen: &mut OpenApiGenerator) -> Result<RequestBody>;
+
+    /// Optionally add responses to the Data Guard.
+    /// This can be used for when the data guard could fail before the handler is called,
+    /// for example with a "422 Unprocessable Entity" when the body could not be parsed.
+    fn get_responses(_gen: &mut OpenApiGenerator) -> Result<Responses> {
+        Ok(Responses::default())
+    }
 }
 
 /// This trait is used to document a dynamic part of a path that implements
@@ -54,15 +63,27 @@ pub trait OpenApiFromFormField<'r>: rocket::form::FromFormField<'r> {
 
 /// This trait is used to document multiple query guard segments that implement
 /// [`FromForm`](rocket::form::FromForm).
-/// For example `?<param>` in the route's query part.
+/// For example `?<param>` or `?<param..>` in the route's query part.
 pub trait OpenApiFromForm<'r>: rocket::form::FromForm<'r> {
     /// Return a [`Vec<Parameter>`] containing the information required to document the
     /// [`FromForm`](rocket::form::FromForm) route's query part.
+    /// Used for `?<param..>`, so the fields of a structure are separate parameters.
     fn form_multi_parameter(
         gen: &mut OpenApiGenerator,
         name: String,
         required: bool,
     ) -> Result<Vec<Parameter>>;
+
+    /// Return the parameters of a named `?<param>` query segment, where the fields of a
+    /// structure are expected as `param.field` or `param[field]`.
+    /// Defaults to [`OpenApiFromForm::form_multi_parameter`].
+    fn form_named_parameter(
+        gen: &mut OpenApiGenerator,
+        name: String,
+        required: bool,
+    ) -> Result<Vec<Parameter>> {
+        Self::form_multi_parameter(gen, name, required)
+    }
 }
 
 /// Used as a return type for [`OpenApiFromRequest`] trait.
@@ -95,7 +116,7 @@ pub enum RequestHeaderInput {
 }
 
 // Re-export derive trait here for convenience.
-pub use rocket_okapi_codegen::OpenApiFromRequest;
+pub use rocket_okapi_codegen::{FormSchema, OpenApiFromRequest};
 
 /// Trait that needs to be implemented for all types that implement
 /// [`FromRequest`](rocket::request::FromRequest).
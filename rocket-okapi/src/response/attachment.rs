@@ -0,0 +1,62 @@
+use super::{resolved_responses, OpenApiResponderInner};
+use crate::gen::OpenApiGenerator;
+use crate::util::{add_success_response_header, string_header};
+use okapi::openapi3::Responses;
+use rocket::request::Request;
+use rocket::response::{self, Responder, Response};
+
+/// Sends the response of `R` as a download: the `Content-Disposition` header is set to
+/// `attachment` with the given file name, so browsers save the body instead of displaying it.
+///
+/// ```rust,ignore
+/// use rocket::fs::NamedFile;
+/// use rocket_okapi::response::Attachment;
+///
+/// #[openapi]
+/// #[get("/report")]
+/// async fn report() -> Option<Attachment<NamedFile>> {
+///     let file = NamedFile::open("reports/latest.pdf").await.ok()?;
+///     Some(Attachment::new("report.pdf", file))
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Attachment<R> {
+    file_name: String,
+    responder: R,
+}
+
+impl<R> Attachment<R> {
+    /// Send the response of `responder` as a file with the name `file_name`.
+    pub fn new(file_name: impl Into<String>, responder: R) -> Self {
+        Attachment {
+            file_name: file_name.into(),
+            responder,
+        }
+    }
+}
+
+impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for Attachment<R> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        let file_name = self.file_name.replace('\\', "\\\\").replace('"', "\\\"");
+        Response::build_from(self.responder.respond_to(req)?)
+            .raw_header(
+                "Content-Disposition",
+                format!("attachment; filename=\"{file_name}\""),
+            )
+            .ok()
+    }
+}
+
+/// The successful responses of `R` document the `Content-Disposition` header.
+impl<R: OpenApiResponderInner> OpenApiResponderInner for Attachment<R> {
+    fn responses(gen: &mut OpenApiGenerator) -> crate::Result<Responses> {
+        let mut responses = resolved_responses::<R>(gen)?;
+        let header = string_header(
+            "The file name of the download.",
+            true,
+            Some("attachment; filename=\"report.pdf\""),
+        );
+        add_success_response_header(&mut responses, "Content-Disposition", header)?;
+        Ok(responses)
+    }
+}
@@ -0,0 +1,73 @@
+use super::OpenApiResponderInner;
+use crate::{gen::OpenApiGenerator, util::add_schema_response};
+use okapi::openapi3::Responses;
+use rocket::futures::{future, Stream, StreamExt};
+use rocket::http::ContentType;
+use rocket::request::Request;
+use rocket::response::{self, stream::ByteStream, Responder, Response};
+use schemars::JsonSchema;
+use serde::Serialize;
+
+/// A (potentially infinite) responder that streams every item of `S` as a line of JSON
+/// ([NDJSON](https://github.com/ndjson/ndjson-spec)). The response `Content-Type` is set to
+/// `application/x-ndjson`.
+///
+/// Items that can not be serialized are logged and skipped, as the status code has already been
+/// sent at that point.
+///
+/// ```rust,ignore
+/// use rocket::futures::stream::{self, Stream};
+/// use rocket_okapi::response::JsonLines;
+///
+/// #[openapi]
+/// #[get("/export")]
+/// fn export() -> JsonLines<impl Stream<Item = Record>> {
+///     JsonLines(stream::iter(load_records()))
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct JsonLines<S>(pub S);
+
+impl<S> From<S> for JsonLines<S> {
+    fn from(stream: S) -> Self {
+        JsonLines(stream)
+    }
+}
+
+impl<'r, S> Responder<'r, 'r> for JsonLines<S>
+where
+    S: Stream + Send + 'r,
+    S::Item: Serialize,
+{
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'r> {
+        let lines = self.0.filter_map(|item| {
+            future::ready(match serde_json::to_vec(&item) {
+                Ok(mut line) => {
+                    line.push(b'\n');
+                    Some(line)
+                }
+                Err(err) => {
+                    log::error!("Could not serialize item of JSON lines stream: {err}");
+                    None
+                }
+            })
+        });
+        Response::build_from(ByteStream(lines).respond_to(req)?)
+            .header(ContentType::new("application", "x-ndjson"))
+            .ok()
+    }
+}
+
+/// Every line of the response body is documented with the schema of `S::Item`.
+impl<S> OpenApiResponderInner for JsonLines<S>
+where
+    S: Stream,
+    S::Item: JsonSchema,
+{
+    fn responses(gen: &mut OpenApiGenerator) -> crate::Result<Responses> {
+        let mut responses = Responses::default();
+        let schema = gen.json_schema::<S::Item>();
+        add_schema_response(&mut responses, 200, "application/x-ndjson", schema)?;
+        Ok(responses)
+    }
+}
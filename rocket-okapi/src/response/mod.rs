@@ -1,9 +1,15 @@
+mod attachment;
+mod json_lines;
 mod responder_impls;
 
 use super::gen::OpenApiGenerator;
 use super::Result;
 use okapi::openapi3::Responses;
 
+pub use attachment::Attachment;
+pub use json_lines::JsonLines;
+pub use rocket_okapi_codegen::{OpenApiErrorResponses, OpenApiResponder};
+
 /// See `OpenApiResponderInner`. This is a wrapper around
 /// `OpenApiResponderInner` that ensures the implementor is a
 /// `rocket::response::Responder`.
@@ -23,10 +29,27 @@ where
     }
 }
 
+/// The responses of `R`, with the `$ref`s to registered responses resolved by
+/// [`OpenApiGenerator::resolve_response_refs`], so wrapping responders can change them.
+fn resolved_responses<R: OpenApiResponderInner + ?Sized>(
+    gen: &mut OpenApiGenerator,
+) -> Result<Responses> {
+    let mut responses = R::responses(gen)?;
+    gen.resolve_response_refs(&mut responses);
+    Ok(responses)
+}
+
 /// Implementing this trait means that any route returning the implementer can
 /// be marked with `#[openapi]`, and that the route can be documented.
 pub trait OpenApiResponderInner {
     /// Create the responses type, which is a list of responses that can be
     /// rendered in `openapi.json` format.
     fn responses(gen: &mut OpenApiGenerator) -> Result<Responses>;
+
+    /// Whether the successful response is a file of which Rocket sets the `Content-Type` from
+    /// its extension, like `NamedFile`. The `#[openapi]` macro then documents the content type of
+    /// routes whose path ends with a file name, like `/favicon.ico`. The default is `false`.
+    fn sends_named_file() -> bool {
+        false
+    }
 }
@@ -1,9 +1,11 @@
-use super::OpenApiResponderInner;
+use super::{resolved_responses, OpenApiResponderInner};
 use crate::{
     gen::OpenApiGenerator,
     util::{
-        add_default_response_code, add_schema_response, change_all_responses_to_default,
-        ensure_status_code_exists, produce_any_responses, set_content_type, set_status_code,
+        add_default_response_code, add_header_to_all_responses, add_response_header,
+        add_schema_response, add_success_response_header, change_all_responses_to_default,
+        ensure_status_code_exists, produce_any_responses, set_content_type,
+        set_response_description, set_status_code, set_status_code_with_description, string_header,
     },
 };
 use okapi::openapi3::Responses;
@@ -78,8 +80,8 @@ where
     R: OpenApiResponderInner,
 {
     fn responses(gen: &mut OpenApiGenerator) -> Result {
-        let left_responses = L::responses(gen)?;
-        let right_responses = R::responses(gen)?;
+        let left_responses = resolved_responses::<L>(gen)?;
+        let right_responses = resolved_responses::<R>(gen)?;
         produce_any_responses(left_responses, right_responses)
     }
 }
@@ -87,8 +89,10 @@ where
 // The ContentType can be set at runtime, so no way of knowing what the mime-type is up front.
 impl<R: OpenApiResponderInner> OpenApiResponderInner for (rocket::http::ContentType, R) {
     fn responses(gen: &mut OpenApiGenerator) -> Result {
-        let mut responses = R::responses(gen)?;
+        let mut responses = resolved_responses::<R>(gen)?;
         set_content_type(&mut responses, rocket::http::ContentType::Any)?;
+        let header = string_header("The media type of the content.", true, None);
+        add_header_to_all_responses(&mut responses, "Content-Type", header)?;
         Ok(responses)
     }
 }
@@ -97,7 +101,7 @@ impl<R: OpenApiResponderInner> OpenApiResponderInner for (rocket::http::ContentT
 // This will add "default" response.
 impl<R: OpenApiResponderInner> OpenApiResponderInner for (rocket::http::Status, R) {
     fn responses(gen: &mut OpenApiGenerator) -> Result {
-        let mut responses = R::responses(gen)?;
+        let mut responses = resolved_responses::<R>(gen)?;
         change_all_responses_to_default(&mut responses);
         Ok(responses)
     }
@@ -108,7 +112,7 @@ where
     T: OpenApiResponderInner + Clone,
 {
     fn responses(gen: &mut OpenApiGenerator) -> Result {
-        let mut responses = T::responses(gen)?;
+        let mut responses = resolved_responses::<T>(gen)?;
         ensure_status_code_exists(&mut responses, 200);
         Ok(responses)
     }
@@ -125,9 +129,22 @@ impl OpenApiResponderInner for Value {
     }
 }
 
+// The Content-Type is determined by the extension of the file at runtime.
+// Rocket 0.5 does not send `Last-Modified`, so it is not documented.
 impl OpenApiResponderInner for rocket::fs::NamedFile {
     fn responses(gen: &mut OpenApiGenerator) -> Result {
-        <Vec<u8>>::responses(gen)
+        let mut responses = <Vec<u8>>::responses(gen)?;
+        let header = string_header(
+            "The media type of the file, based on its extension.",
+            false,
+            None,
+        );
+        add_success_response_header(&mut responses, "Content-Type", header)?;
+        Ok(responses)
+    }
+
+    fn sends_named_file() -> bool {
+        true
     }
 }
 
@@ -163,7 +180,7 @@ impl OpenApiResponderInner for String {
 impl OpenApiResponderInner for Vec<u8> {
     fn responses(gen: &mut OpenApiGenerator) -> Result {
         let mut responses = Responses::default();
-        let schema = gen.json_schema::<Vec<u8>>();
+        let schema = gen.binary_schema();
         add_schema_response(&mut responses, 200, "application/octet-stream", schema)?;
         Ok(responses)
     }
@@ -172,7 +189,11 @@ impl OpenApiResponderInner for Vec<u8> {
 impl OpenApiResponderInner for rocket::response::status::NoContent {
     fn responses(_gen: &mut OpenApiGenerator) -> Result {
         let mut responses = Responses::default();
-        set_status_code(&mut responses, 204)?;
+        set_status_code_with_description(
+            &mut responses,
+            204,
+            "The request succeeded without a response body.",
+        )?;
         Ok(responses)
     }
 }
@@ -181,16 +202,23 @@ impl OpenApiResponderInner for rocket::response::Redirect {
     fn responses(_gen: &mut OpenApiGenerator) -> Result {
         let mut responses = Responses::default();
         // Add all possible status codes.
-        set_status_code(&mut responses, 301)?; // Moved Permanently
-        set_status_code(&mut responses, 302)?; // Found
-        set_status_code(&mut responses, 303)?; // See Other
-        set_status_code(&mut responses, 307)?; // Temporary Redirect
-        set_status_code(&mut responses, 308)?; // Permanent Redirect
+        let redirects = [
+            (301, "The resource has moved permanently to the `Location`."), // Moved Permanently
+            (302, "The resource is temporarily at the `Location`."),        // Found
+            (303, "The response can be found at the `Location`."),          // See Other
+            (307, "Repeat the request at the `Location`."),                 // Temporary Redirect
+            (308, "Repeat this and future requests at the `Location`."),    // Permanent Redirect
+        ];
+        for (status, description) in redirects {
+            set_response_description(&mut responses, status, description)?;
+            let header = string_header("The URI to redirect to.", true, None);
+            add_response_header(&mut responses, status, "Location", header)?;
+        }
 
         // According to Rocket docs:
         // > If the URI value used to create the `Responder` is an invalid URI,
         // > an error of `Status::InternalServerError` is returned.
-        set_status_code(&mut responses, 500)?; // Internal Server Error
+        set_response_description(&mut responses, 500, "The redirect URI is invalid.")?;
         Ok(responses)
     }
 }
@@ -201,18 +229,29 @@ where
     E: OpenApiResponderInner,
 {
     fn responses(gen: &mut OpenApiGenerator) -> Result {
-        let ok_responses = T::responses(gen)?;
-        let err_responses = E::responses(gen)?;
+        let ok_responses = resolved_responses::<T>(gen)?;
+        let err_responses = resolved_responses::<E>(gen)?;
         produce_any_responses(ok_responses, err_responses)
     }
+
+    fn sends_named_file() -> bool {
+        T::sends_named_file()
+    }
 }
 
 impl<R: OpenApiResponderInner> OpenApiResponderInner for Option<R> {
     fn responses(gen: &mut OpenApiGenerator) -> Result {
-        let mut responses = R::responses(gen)?;
-        ensure_status_code_exists(&mut responses, 404);
+        let mut responses = resolved_responses::<R>(gen)?;
+        // `None` responds with `404 Not Found`, unless `R` documents that status itself.
+        if !responses.responses.contains_key("404") {
+            set_response_description(&mut responses, 404, "The resource was not found.")?;
+        }
         Ok(responses)
     }
+
+    fn sends_named_file() -> bool {
+        R::sends_named_file()
+    }
 }
 
 macro_rules! response_content_wrapper {
@@ -221,7 +260,7 @@ macro_rules! response_content_wrapper {
             for rocket::response::content::$responder<R>
         {
             fn responses(gen: &mut OpenApiGenerator) -> Result {
-                let mut responses = R::responses(gen)?;
+                let mut responses = resolved_responses::<R>(gen)?;
                 set_content_type(&mut responses, $mime)?;
                 Ok(responses)
             }
@@ -244,7 +283,7 @@ macro_rules! status_responder {
             R: OpenApiResponderInner + Send,
         {
             fn responses(gen: &mut OpenApiGenerator) -> Result {
-                let mut responses = R::responses(gen)?;
+                let mut responses = resolved_responses::<R>(gen)?;
                 set_status_code(&mut responses, $status)?;
                 Ok(responses)
             }
@@ -255,18 +294,36 @@ macro_rules! status_responder {
 status_responder!(Accepted, 202);
 status_responder!(BadRequest, 400);
 status_responder!(Conflict, 409);
-status_responder!(Created, 201);
 status_responder!(Custom, 0);
 status_responder!(Forbidden, 403);
 status_responder!(NotFound, 404);
 status_responder!(Unauthorized, 401);
 
+impl<R> OpenApiResponderInner for rocket::response::status::Created<R>
+where
+    R: OpenApiResponderInner + Send,
+{
+    fn responses(gen: &mut OpenApiGenerator) -> Result {
+        let mut responses = resolved_responses::<R>(gen)?;
+        set_status_code_with_description(&mut responses, 201, "The resource was created.")?;
+        let location = string_header("The URI of the created resource.", true, None);
+        add_response_header(&mut responses, 201, "Location", location)?;
+        // Only set when the body is added with `Created::tagged_body`.
+        let etag = string_header("The hash of the body.", false, None);
+        add_response_header(&mut responses, 201, "ETag", etag)?;
+        Ok(responses)
+    }
+}
+
 impl<R> OpenApiResponderInner for rocket::response::Flash<R>
 where
     R: OpenApiResponderInner,
 {
     fn responses(gen: &mut OpenApiGenerator) -> Result {
-        R::responses(gen)
+        let mut responses = resolved_responses::<R>(gen)?;
+        let header = string_header("Sets the `_flash` cookie with the message.", true, None);
+        add_header_to_all_responses(&mut responses, "Set-Cookie", header)?;
+        Ok(responses)
     }
 }
 
@@ -344,7 +401,7 @@ where
 impl<S> OpenApiResponderInner for rocket::response::stream::EventStream<S> {
     fn responses(gen: &mut OpenApiGenerator) -> Result {
         let mut responses = Responses::default();
-        let schema = gen.json_schema::<Vec<u8>>();
+        let schema = gen.binary_schema();
         add_schema_response(&mut responses, 200, "text/event-stream", schema)?;
         Ok(responses)
     }
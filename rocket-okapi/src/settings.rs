@@ -1,5 +1,12 @@
+use crate::gen::OpenApiGenerator;
+use okapi::openapi3::{Info, Object, OpenApi, SecurityRequirement, Server, Tag};
+use rocket::data::Limits;
+use rocket::figment::Figment;
+use rocket::http::Method;
 use schemars::generate::SchemaSettings;
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Arc;
 
 /// Settings which are used to customize the behavior of the `OpenApiGenerator`.
 #[derive(Debug, Clone)]
@@ -9,6 +16,97 @@ pub struct OpenApiSettings {
     /// The path to the json file that contains the API specification. Then default is
     /// `openapi.json`.
     pub json_path: String,
+    /// The path to the json file that contains the AsyncAPI specification of the WebSocket and
+    /// Server-Sent Events routes. The default is `asyncapi.json`.
+    pub asyncapi_json_path: String,
+    /// How routes with the same path and method are documented. Rocket allows this when the
+    /// routes have a different `rank`. The default is [`RankedRoutes::Merge`].
+    pub ranked_routes: RankedRoutes,
+    /// The `Info` of the specification. When `None` (the default) it is generated from the
+    /// package information of the crate (`CARGO_PKG_*`).
+    pub info: Option<Info>,
+    /// The servers that are added to the specification.
+    pub servers: Vec<Server>,
+    /// Declarations of the tags used by the routes, with their description and external docs.
+    pub tags: Vec<Tag>,
+    /// The security requirements that apply to all routes. Routes can still list their own.
+    pub security: Vec<SecurityRequirement>,
+    /// The OpenAPI version set in the `openapi` field of the specification. The default is
+    /// `3.0.0`. For OpenAPI 3.1 and later the JSON Schemas follow JSON Schema 2020-12: the
+    /// transforms of `schema_settings` that are only needed for OpenAPI 3.0, like `AddNullable`
+    /// and `SetSingleExample`, are not applied.
+    pub openapi_version: String,
+    /// Extensions (`x-...` fields) added to the root of the specification.
+    pub extensions: Object,
+    /// How the `operationId` of routes without an explicit `#[openapi(operation_id = "...")]` is
+    /// created. The default is [`OperationIdStrategy::ModuleQualified`].
+    pub operation_id_strategy: OperationIdStrategy,
+    /// The Rocket catchers whose responses are documented, created with
+    /// `openapi_catchers![...]`. The responses are added to `components.responses`.
+    pub catchers: Vec<OpenApiCatcher>,
+    /// To which operations the responses of the `catchers` are added. The default is
+    /// [`CatcherResponses::MatchingStatusCodes`].
+    pub catcher_responses: CatcherResponses,
+    /// The size limits of request bodies, as configured in Rocket. When set, request bodies
+    /// document their limit with `x-max-body-size` (and `maxLength` for strings) and the error
+    /// responses mention it. The default is `None`, so no limits are documented.
+    /// See [`OpenApiSettings::with_body_limits_from`].
+    pub body_limits: Option<Limits>,
+    /// How a response schema that accepts the schemas of several responders is documented, like
+    /// the `200` response of `Either<Json<A>, Json<B>>`. The default is [`SchemaUnions::AnyOf`].
+    pub schema_unions: SchemaUnions,
+}
+
+/// A function that adds the documentation of a catcher to the `OpenApiGenerator`.
+/// A list of them is created with `openapi_catchers![...]`.
+pub type OpenApiCatcher = fn(&mut OpenApiGenerator) -> crate::Result<()>;
+
+/// Determines to which operations the responses of catchers are added.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CatcherResponses {
+    /// Only error responses (`4XX` and `5XX`) that an operation already documents without
+    /// `content` use the catcher for that status code, or else the default catcher. These are
+    /// the responses of a `Status` error or a failing guard, for which Rocket calls a catcher.
+    /// Responses with `content`, like a `BadRequest<String>`, are sent by the route and kept.
+    #[default]
+    MatchingStatusCodes,
+    /// Like `MatchingStatusCodes`, but the responses of all catchers are also added to every
+    /// operation, for the status codes it does not document yet. The default catcher is used for
+    /// the `default` response.
+    AllOperations,
+}
+
+/// Determines how routes with the same path and method are documented.
+/// See: <https://rocket.rs/v0.5/guide/requests/#forwarding>
+///
+/// Routes that only differ by `format` are always merged into one operation with several media
+/// types.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RankedRoutes {
+    /// Merge the parameters, request bodies and responses of all routes into a single operation.
+    /// Schemas that differ between the routes are combined using `oneOf`.
+    #[default]
+    Merge,
+    /// Only document the route that is added first, the order of the routes in
+    /// `openapi_get_routes!` and similar macros.
+    KeepFirst,
+    /// Fail the generation of the specification with an `OpenApiError`.
+    Error,
+}
+
+/// Determines how a response schema that accepts the schemas of several responders is
+/// documented. Nested unions are always flattened, and schemas are only listed once.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SchemaUnions {
+    /// Combine the schemas with `anyOf`.
+    #[default]
+    AnyOf,
+    /// Combine the schemas with `oneOf` when a value can only match one of them. When all schemas
+    /// are references to types with the same tag property, like enums with
+    /// `#[serde(tag = "...")]`, a `discriminator` with the tag values is added. References to
+    /// types with a different `type`, like a struct and a sequence, need no discriminator.
+    /// Other schemas are still combined with `anyOf`.
+    OneOf,
 }
 
 impl Default for OpenApiSettings {
@@ -16,6 +114,19 @@ impl Default for OpenApiSettings {
         OpenApiSettings {
             schema_settings: SchemaSettings::openapi3(),
             json_path: "/openapi.json".to_owned(),
+            asyncapi_json_path: "/asyncapi.json".to_owned(),
+            ranked_routes: RankedRoutes::default(),
+            info: None,
+            servers: Vec::new(),
+            tags: Vec::new(),
+            security: Vec::new(),
+            openapi_version: OpenApi::default_version(),
+            extensions: Object::default(),
+            operation_id_strategy: OperationIdStrategy::default(),
+            catchers: Vec::new(),
+            catcher_responses: CatcherResponses::default(),
+            body_limits: None,
+            schema_unions: SchemaUnions::default(),
         }
     }
 }
@@ -28,6 +139,98 @@ impl OpenApiSettings {
             ..OpenApiSettings::default()
         }
     }
+
+    /// Document the body size limits that are configured in the `limits` of the Rocket
+    /// configuration, for example `rocket.figment()`. Rocket's default limits are used when the
+    /// configuration does not contain (valid) limits.
+    #[must_use]
+    pub fn with_body_limits_from(mut self, figment: &Figment) -> Self {
+        self.body_limits = Some(figment.extract_inner("limits").unwrap_or_default());
+        self
+    }
+}
+
+/// Determines the `operationId` of a route that does not set one explicitly.
+/// The examples are for `post::create_post`, handling `POST /posts/<id>`.
+#[derive(Clone, Default)]
+pub enum OperationIdStrategy {
+    /// The name of the function: `create_post`.
+    FunctionName,
+    /// The path of the function as given to `openapi_get_routes!` and similar macros, with `::`
+    /// replaced by `_`: `post_create_post`.
+    #[default]
+    ModuleQualified,
+    /// The module qualified name in camelCase: `postCreatePost`.
+    CamelCase,
+    /// The method and path of the route: `post_posts_id`.
+    MethodAndPath,
+    /// A custom function.
+    Custom(Arc<dyn Fn(&OperationIdInput<'_>) -> String + Send + Sync>),
+}
+
+impl OperationIdStrategy {
+    /// Create the `operationId` for a route.
+    #[must_use]
+    pub fn operation_id(&self, input: &OperationIdInput<'_>) -> String {
+        match self {
+            OperationIdStrategy::FunctionName => input.function_name.to_owned(),
+            OperationIdStrategy::ModuleQualified => input.qualified_name.to_owned(),
+            OperationIdStrategy::CamelCase => to_camel_case(input.qualified_name),
+            OperationIdStrategy::MethodAndPath => {
+                let mut id = input.method.as_str().to_ascii_lowercase();
+                for word in input.path.split(|c: char| !c.is_ascii_alphanumeric()) {
+                    if !word.is_empty() {
+                        id.push('_');
+                        id.push_str(word);
+                    }
+                }
+                id
+            }
+            OperationIdStrategy::Custom(f) => f(input),
+        }
+    }
+}
+
+impl fmt::Debug for OperationIdStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OperationIdStrategy::FunctionName => write!(f, "FunctionName"),
+            OperationIdStrategy::ModuleQualified => write!(f, "ModuleQualified"),
+            OperationIdStrategy::CamelCase => write!(f, "CamelCase"),
+            OperationIdStrategy::MethodAndPath => write!(f, "MethodAndPath"),
+            OperationIdStrategy::Custom(_) => write!(f, "Custom(..)"),
+        }
+    }
+}
+
+/// The information about a route that is used to create its `operationId`.
+#[derive(Debug, Clone, Copy)]
+pub struct OperationIdInput<'a> {
+    /// The name of the function, for example `create_post`.
+    pub function_name: &'a str,
+    /// The path of the function as given to `openapi_get_routes!` and similar macros, with `::`
+    /// replaced by `_`. For example `post_create_post`.
+    pub qualified_name: &'a str,
+    /// The HTTP method of the route.
+    pub method: Method,
+    /// The path of the route in OpenAPI format, for example `/posts/{id}`.
+    pub path: &'a str,
+}
+
+fn to_camel_case(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    let mut upper = false;
+    for c in name.trim_start_matches(['_', ':']).chars() {
+        if c == '_' || c == ':' {
+            upper = true;
+        } else if upper {
+            result.push(c.to_ascii_uppercase());
+            upper = false;
+        } else {
+            result.push(c);
+        }
+    }
+    result
 }
 
 /// Contains a named url.
@@ -1,5 +1,8 @@
 use crate::{OpenApiError, Result};
-use okapi::openapi3::{MediaType, RefOr, Response, Responses, SchemaObject};
+use okapi::openapi3::{
+    Header, MediaType, Object, Parameter, ParameterValue, RefOr, RequestBody, Response, Responses,
+    SchemaObject,
+};
 use okapi::Map;
 
 // FIXME this whole file is a huge mess...
@@ -8,6 +11,19 @@ use okapi::Map;
 /// response in the `Responses`.
 pub fn set_status_code(responses: &mut Responses, status: u16) -> Result<()> {
     let old_responses = std::mem::take(&mut responses.responses);
+    // A single response is moved as is, so it can be a `$ref`.
+    if old_responses.len() == 1 {
+        if let Some(response) = old_responses.into_values().next() {
+            // Use `0` as `default`
+            let key = if status == 0 {
+                "default".to_owned()
+            } else {
+                status.to_string()
+            };
+            responses.responses.insert(key, response);
+        }
+        return Ok(());
+    }
     // Use `0` as `default`
     let new_response = if status == 0 {
         ensure_not_ref(add_default_response_code(responses))?
@@ -21,6 +37,70 @@ pub fn set_status_code(responses: &mut Responses, status: u16) -> Result<()> {
     Ok(())
 }
 
+/// Like `set_status_code`, but the response gets the given `description` instead of the
+/// descriptions of the responses it combines.
+pub fn set_status_code_with_description(
+    responses: &mut Responses,
+    status: u16,
+    description: &str,
+) -> Result<()> {
+    set_status_code(responses, status)?;
+    let key = if status == 0 {
+        "default".to_owned()
+    } else {
+        status.to_string()
+    };
+    if let Some(response) = responses.responses.get_mut(&key) {
+        ensure_not_ref(response)?.description = description.to_owned();
+    }
+    Ok(())
+}
+
+/// Sets the `description` of the response with the given `status` code. The response is added
+/// when there is none with this status code yet, for responses that are only documented.
+pub fn set_response_description(
+    responses: &mut Responses,
+    status: u16,
+    description: &str,
+) -> Result<()> {
+    ensure_not_ref(ensure_status_code_exists(responses, status))?.description =
+        description.to_owned();
+    Ok(())
+}
+
+/// The description of a response without one: the reason phrase of its status code, like
+/// `Not Found` for `404`. `status` can also be a range like `4XX` or `default`.
+#[must_use]
+pub fn default_description(status: &str) -> String {
+    let description = match status {
+        "default" => "Default response",
+        "1XX" => "Informational",
+        "2XX" => "Success",
+        "3XX" => "Redirection",
+        "4XX" => "Client error",
+        "5XX" => "Server error",
+        _ => status
+            .parse()
+            .ok()
+            .and_then(rocket::http::Status::from_code)
+            .and_then(|status| status.reason())
+            .unwrap_or("Response"),
+    };
+    description.to_owned()
+}
+
+/// Sets the description of all responses without one to `default_description`, because
+/// OpenAPI requires a description.
+pub fn set_default_descriptions(responses: &mut Responses) {
+    for (status, response) in responses.responses.iter_mut() {
+        if let RefOr::Object(response) = response {
+            if response.description.is_empty() {
+                response.description = default_description(status);
+            }
+        }
+    }
+}
+
 /// Checks if the provided `status` code is in the `responses.responses` field. If it isn't, inserts
 /// it.
 pub fn ensure_status_code_exists(responses: &mut Responses, status: u16) -> &mut RefOr<Response> {
@@ -123,6 +203,135 @@ pub fn set_content_type(responses: &mut Responses, content_type: impl ToString)
     Ok(())
 }
 
+/// Replaces the Content-Type of all successful (`2XX` and `default`) responses with
+/// `content_type`, for routes with a `format` on a method without payload.
+/// Responses without any content, like most error responses, are left untouched.
+pub fn set_success_content_type(responses: &mut Responses, content_type: &str) -> Result<()> {
+    for (status, resp_refor) in responses.responses.iter_mut() {
+        if status.starts_with('2') || status == "default" {
+            let response = ensure_not_ref(resp_refor)?;
+            if !response.content.is_empty() {
+                replace_content_type(&mut response.content, content_type);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Replaces the Content-Type of the request body with `content_type`, for routes with a `format`
+/// on a method with payload.
+pub fn set_request_body_content_type(
+    request_body: &mut RefOr<RequestBody>,
+    content_type: &str,
+) -> Result<()> {
+    match request_body {
+        RefOr::Ref(_) => Err(OpenApiError::new(
+            "Altering Ref request bodies is not supported.".to_owned(),
+        )),
+        RefOr::Object(request_body) => {
+            replace_content_type(&mut request_body.content, content_type);
+            Ok(())
+        }
+    }
+}
+
+/// Sets the `example` of all media types of the request body to `example`.
+pub fn set_request_body_example(request_body: &mut RequestBody, example: serde_json::Value) {
+    for media in request_body.content.values_mut() {
+        media.example = Some(example.clone());
+    }
+}
+
+/// Sets the `example` of all media types of the successful (`2XX` and `default`) responses that
+/// do not have an example yet. Responses that are a `$ref` are left untouched.
+pub fn set_success_example(responses: &mut Responses, example: serde_json::Value) {
+    for (status, response) in responses.responses.iter_mut() {
+        let RefOr::Object(response) = response else {
+            continue;
+        };
+        if status.starts_with('2') || status == "default" {
+            for media in response.content.values_mut() {
+                if media.example.is_none() && media.examples.is_none() {
+                    media.example = Some(example.clone());
+                }
+            }
+        }
+    }
+}
+
+/// Sets the `example` of all media types of the response with the given `status` code.
+/// Returns an error if there is no response with this status code, or if that response has no
+/// `content`.
+pub fn set_response_example(
+    responses: &mut Responses,
+    status: u16,
+    example: serde_json::Value,
+) -> Result<()> {
+    let response = responses
+        .responses
+        .get_mut(&status.to_string())
+        .ok_or_else(|| {
+            OpenApiError::new(format!(
+                "Can not add an example to the `{status}` response, because it is not documented."
+            ))
+        })?;
+    let response = ensure_not_ref(response)?;
+    if response.content.is_empty() {
+        return Err(OpenApiError::new(format!(
+            "Can not add an example to the `{status}` response, because it has no content."
+        )));
+    }
+    for media in response.content.values_mut() {
+        media.example = Some(example.clone());
+    }
+    Ok(())
+}
+
+/// Sets the documentation of a parameter: the `description` and `example` (when given) and
+/// marks it as deprecated when `deprecated` is `true`.
+pub fn set_parameter_docs(
+    parameter: &mut Parameter,
+    description: Option<&str>,
+    example: Option<serde_json::Value>,
+    deprecated: bool,
+) {
+    if let Some(description) = description {
+        parameter.description = Some(description.to_owned());
+    }
+    if let Some(example) = example {
+        match &mut parameter.value {
+            ParameterValue::Schema {
+                example: parameter_example,
+                examples,
+                ..
+            } => {
+                *parameter_example = Some(example);
+                *examples = None;
+            }
+            ParameterValue::Content { content } => {
+                for media in content.values_mut() {
+                    media.example = Some(example.clone());
+                    media.examples = None;
+                }
+            }
+        }
+    }
+    parameter.deprecated |= deprecated;
+}
+
+/// Keeps the `MediaType` that is already documented for `content_type` or combines all of them
+/// into a single `MediaType` for `content_type`.
+fn replace_content_type(content: &mut Map<String, MediaType>, content_type: &str) {
+    let mt = match content.swap_remove(content_type) {
+        Some(mt) => mt,
+        None => content.values().fold(MediaType::default(), |mt, mt2| {
+            accept_either_media_type(mt, mt2.clone())
+        }),
+    };
+    content.clear();
+    content.insert(content_type.to_owned(), mt);
+}
+
 /// Adds a `Response` to a `Responses` object with the given status code, Content-Type and `SchemaObject`.
 pub fn add_schema_response(
     responses: &mut Responses,
@@ -137,6 +346,167 @@ pub fn add_schema_response(
     add_content_response(responses, status, content_type, media)
 }
 
+/// Adds a response with the given status code, `description` and the Content-Type and
+/// `SchemaObject` of each body it can have. Schemas with the same Content-Type are combined. The
+/// response is combined with an existing response with the same status code.
+pub fn add_described_response(
+    responses: &mut Responses,
+    status: u16,
+    description: &str,
+    content: Vec<(&str, SchemaObject)>,
+) -> Result<()> {
+    let mut response = Response {
+        description: description.to_owned(),
+        ..Response::default()
+    };
+    for (content_type, schema) in content {
+        let media = MediaType {
+            schema: Some(schema),
+            ..MediaType::default()
+        };
+        add_media_type(&mut response.content, content_type, media);
+    }
+    let mut new_responses = Responses::default();
+    new_responses
+        .responses
+        .insert(status.to_string(), response.into());
+    *responses = produce_any_responses(std::mem::take(responses), new_responses)?;
+    Ok(())
+}
+
+/// Describes the events of all `text/event-stream` responses using an `x-itemSchema` extension,
+/// modeled after the `itemSchema` field of OpenAPI 3.2.
+///
+/// The `data_schema` is the schema of the JSON in the `data` field of every event.
+/// When `event_names` is not empty, the `event` field is limited to these names.
+/// The `id_description` explains how the `id` field (and `Last-Event-ID` header) is used.
+pub fn set_event_stream_item_schema(
+    responses: &mut Responses,
+    data_schema: SchemaObject,
+    event_names: &[&str],
+    id_description: Option<&str>,
+) -> Result<()> {
+    let mut event = serde_json::json!({ "type": "string" });
+    if !event_names.is_empty() {
+        event["enum"] = serde_json::json!(event_names);
+    }
+    let mut id = serde_json::json!({ "type": "string" });
+    if let Some(id_description) = id_description {
+        id["description"] = serde_json::json!(id_description);
+    }
+    let item_schema = serde_json::json!({
+        "type": "object",
+        "required": ["data"],
+        "properties": {
+            "data": {
+                "type": "string",
+                "contentMediaType": "application/json",
+                "contentSchema": data_schema,
+            },
+            "event": event,
+            "id": id,
+            "retry": { "type": "integer", "minimum": 0 },
+        },
+    });
+    for response in responses.responses.values_mut() {
+        let response = ensure_not_ref(response)?;
+        if let Some(media) = response.content.get_mut("text/event-stream") {
+            media
+                .extensions
+                .insert("x-itemSchema".to_owned(), item_schema.clone());
+        }
+    }
+    Ok(())
+}
+
+/// Whether one of the successful (`2XX` and `default`) responses is a stream of Server-Sent
+/// Events, with a `text/event-stream` body.
+pub fn has_event_stream_response(responses: &Responses) -> bool {
+    responses.responses.iter().any(|(status, response)| {
+        (status.starts_with('2') || status == "default")
+            && matches!(response, RefOr::Object(response) if response.content.contains_key("text/event-stream"))
+    })
+}
+
+/// Replaces the description of all successful (`2XX` and `default`) responses with
+/// `description`.
+pub fn set_success_response_description(
+    responses: &mut Responses,
+    description: &str,
+) -> Result<()> {
+    for (status, response) in responses.responses.iter_mut() {
+        if status.starts_with('2') || status == "default" {
+            ensure_not_ref(response)?.description = description.to_owned();
+        }
+    }
+    Ok(())
+}
+
+/// Creates a `Header` with a string schema, for documenting response headers.
+pub fn string_header(description: &str, required: bool, example: Option<&str>) -> Header {
+    Header {
+        description: Some(description.to_owned()),
+        required,
+        deprecated: false,
+        allow_empty_value: false,
+        value: ParameterValue::Schema {
+            style: None,
+            explode: None,
+            allow_reserved: false,
+            schema: schemars::json_schema!({ "type": "string" }),
+            example: example.map(Into::into),
+            examples: None,
+        },
+        extensions: Object::default(),
+    }
+}
+
+/// Adds the `header` with the given `name` to the response with the given `status` code.
+/// Does nothing if there is no response with this status code.
+pub fn add_response_header(
+    responses: &mut Responses,
+    status: u16,
+    name: &str,
+    header: Header,
+) -> Result<()> {
+    if let Some(response) = responses.responses.get_mut(&status.to_string()) {
+        insert_header(ensure_not_ref(response)?, name, header);
+    }
+    Ok(())
+}
+
+/// Adds the `header` with the given `name` to all successful (`2XX` and `default`) responses.
+pub fn add_success_response_header(
+    responses: &mut Responses,
+    name: &str,
+    header: Header,
+) -> Result<()> {
+    for (status, response) in responses.responses.iter_mut() {
+        if status.starts_with('2') || status == "default" {
+            insert_header(ensure_not_ref(response)?, name, header.clone());
+        }
+    }
+    Ok(())
+}
+
+/// Adds the `header` with the given `name` to all responses.
+pub fn add_header_to_all_responses(
+    responses: &mut Responses,
+    name: &str,
+    header: Header,
+) -> Result<()> {
+    for response in responses.responses.values_mut() {
+        insert_header(ensure_not_ref(response)?, name, header.clone());
+    }
+    Ok(())
+}
+
+fn insert_header(response: &mut Response, name: &str, header: Header) {
+    response
+        .headers
+        .insert(name.to_owned(), RefOr::Object(header));
+}
+
 /// Merges the the two given `Responses`.
 pub fn produce_any_responses(r1: Responses, r2: Responses) -> Result<Responses> {
     let mut result = Responses {
@@ -157,11 +527,18 @@ pub fn produce_any_responses(r1: Responses, r2: Responses) -> Result<Responses>
     Ok(result)
 }
 
+/// Returns the response if it is not a `$ref`.
+///
+/// `$ref`s to responses registered with `OpenApiGenerator::register_response` are resolved into
+/// a copy of the response by `OpenApiGenerator::resolve_response_refs`, which is done for the
+/// responses of all responders and guards. Other `$ref`s can not be changed.
 fn ensure_not_ref(response: &mut RefOr<Response>) -> Result<&mut Response> {
     match response {
-        RefOr::Ref(_) => Err(OpenApiError::new(
-            "Altering Ref responses is not supported.".to_owned(),
-        )),
+        RefOr::Ref(r) => Err(OpenApiError::new(format!(
+            "Altering the `$ref` response `{}` is not supported, because it is not registered \
+            with `OpenApiGenerator::register_response`.",
+            r.reference
+        ))),
         RefOr::Object(o) => Ok(o),
     }
 }
@@ -172,13 +549,16 @@ fn extend<A, E: Extend<A>>(mut a: E, b: impl IntoIterator<Item = A>) -> E {
 }
 
 fn produce_either_response(r1: Response, r2: Response) -> Response {
-    let description = if r1.description.is_empty() {
-        r2.description
-    } else if r2.description.is_empty() {
-        r1.description
-    } else {
-        format!("{}\n{}", r1.description, r2.description)
-    };
+    // Descriptions that are the same for both responses are only used once.
+    let mut description = r1.description;
+    for line in r2.description.lines() {
+        if !description.lines().any(|existing| existing == line) {
+            if !description.is_empty() {
+                description.push('\n');
+            }
+            description.push_str(line);
+        }
+    }
 
     let mut content = r1.content;
     for (content_type, media) in r2.content {
@@ -194,9 +574,23 @@ fn produce_either_response(r1: Response, r2: Response) -> Response {
     }
 }
 
+/// The extension of a `MediaType` whose schema is an `anyOf` of the schemas of several
+/// responders, so `OpenApiGenerator::into_openapi` can tell it apart from an `anyOf` of a single
+/// type. It is removed from the generated specification.
+pub(crate) const SCHEMA_UNION_EXTENSION: &str = "x-okapi-schema-union";
+
 fn accept_either_media_type(mt1: MediaType, mt2: MediaType) -> MediaType {
+    let union1 = mt1.extensions.contains_key(SCHEMA_UNION_EXTENSION);
+    let union2 = mt2.extensions.contains_key(SCHEMA_UNION_EXTENSION);
+    let (schema, union) = accept_either_schema((mt1.schema, union1), (mt2.schema, union2));
+    let mut extensions = extend(mt1.extensions, mt2.extensions);
+    if union {
+        extensions.insert(SCHEMA_UNION_EXTENSION.to_owned(), true.into());
+    } else {
+        extensions.shift_remove(SCHEMA_UNION_EXTENSION);
+    }
     MediaType {
-        schema: accept_either_schema(mt1.schema, mt2.schema),
+        schema,
         example: mt1.example.or(mt2.example),
         examples: match (mt1.examples, mt2.examples) {
             (Some(e1), Some(e2)) => Some(extend(e1, e2)),
@@ -204,27 +598,49 @@ fn accept_either_media_type(mt1: MediaType, mt2: MediaType) -> MediaType {
             (None, None) => None,
         },
         encoding: extend(mt1.encoding, mt2.encoding),
-        extensions: extend(mt1.extensions, mt2.extensions),
+        extensions,
     }
 }
 
+/// Combines two schemas, each with whether it is a union of the schemas of several responders.
+/// Returns the combined schema and whether it is such a union.
 fn accept_either_schema(
-    s1: Option<SchemaObject>,
-    s2: Option<SchemaObject>,
-) -> Option<SchemaObject> {
+    (s1, union1): (Option<SchemaObject>, bool),
+    (s2, union2): (Option<SchemaObject>, bool),
+) -> (Option<SchemaObject>, bool) {
     let (s1, s2) = match (s1, s2) {
         (Some(s1), Some(s2)) => (s1, s2),
-        (Some(s), None) | (None, Some(s)) => return Some(s),
-        (None, None) => return None,
+        (Some(s), None) => return (Some(s), union1),
+        (None, Some(s)) => return (Some(s), union2),
+        (None, None) => return (None, false),
     };
+    // Set an `anyOf` property with the members of both schemas, without nesting unions or
+    // repeating the same schema.
+    let mut members = union_members(s1, union1);
+    for member in union_members(s2, union2) {
+        if !members.contains(&member) {
+            members.push(member);
+        }
+    }
+    if members.len() == 1 {
+        return (
+            members.pop().and_then(|member| member.try_into().ok()),
+            false,
+        );
+    }
     let mut schema = SchemaObject::default();
-    // Set an `anyOf` property with the two schemas
-    schema.insert(
-        "anyOf".to_owned(),
-        serde_json::Value::Array(vec![
-            serde_json::Value::from(s1),
-            serde_json::Value::from(s2),
-        ]),
-    );
-    Some(schema)
+    schema.insert("anyOf".to_owned(), serde_json::Value::Array(members));
+    (Some(schema), true)
+}
+
+/// The members of a schema that is a union of the schemas of several responders, or else the
+/// schema itself.
+fn union_members(schema: SchemaObject, union: bool) -> Vec<serde_json::Value> {
+    let mut schema = serde_json::Value::from(schema);
+    if union {
+        if let Some(serde_json::Value::Array(members)) = schema.get_mut("anyOf") {
+            return std::mem::take(members);
+        }
+    }
+    vec![schema]
 }
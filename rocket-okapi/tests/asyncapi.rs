@@ -0,0 +1,156 @@
+//! Test that routes declaring `send`/`receive` messages are added to the AsyncAPI spec.
+
+use rocket_okapi::{asyncapi_get_spec, openapi_get_spec};
+
+// These functions are never actually called.
+#[allow(unused)]
+mod endpoints {
+    use rocket::futures::stream::Stream;
+    use rocket::response::stream::{Event, EventStream};
+    use rocket::{get, post, serde::json::Json};
+    use rocket_okapi::{openapi, JsonSchema};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, JsonSchema)]
+    pub struct Tick {
+        pub count: u64,
+    }
+
+    /// Stream of ticks
+    #[openapi(
+        send = "Tick",
+        event = "tick",
+        event = "reset",
+        event_id = "Sequence number, resume with `Last-Event-ID`."
+    )]
+    #[get("/ticks/<interval>")]
+    pub fn ticks(interval: u64) -> EventStream<impl Stream<Item = Event>> {
+        EventStream! {
+            yield Event::json(&Tick { count: interval });
+        }
+    }
+
+    pub type TickStream = EventStream<rocket::futures::stream::Empty<Event>>;
+
+    /// Stream of alarms
+    #[openapi(send = "Tick")]
+    #[get("/alarms")]
+    pub fn alarms() -> TickStream {
+        unimplemented!()
+    }
+
+    /// Sets an alarm
+    #[openapi(receive = "Tick")]
+    #[post("/alarms", data = "<tick>")]
+    pub fn set_alarm(tick: Json<Tick>) {}
+
+    /// Another stream of alarms
+    #[openapi(send = "Tick")]
+    #[get("/alarms?<all>")]
+    pub fn all_alarms(all: bool) -> TickStream {
+        unimplemented!()
+    }
+
+    #[openapi]
+    #[get("/last_tick")]
+    pub fn last_tick() -> Json<Tick> {
+        Json(Tick { count: 0 })
+    }
+}
+
+#[test]
+fn only_routes_with_messages_are_channels() {
+    let spec = asyncapi_get_spec![endpoints::ticks, endpoints::last_tick];
+
+    assert_eq!(spec.asyncapi, "2.6.0");
+    assert_eq!(spec.channels.len(), 1);
+    let channel = &spec.channels["/ticks/{interval}"];
+    assert!(channel.parameters.contains_key("interval"));
+    assert!(channel.publish.is_none());
+    let subscribe = channel.subscribe.as_ref().unwrap();
+    assert_eq!(subscribe.summary.as_deref(), None);
+    assert_eq!(subscribe.description.as_deref(), Some("Stream of ticks"));
+    assert!(subscribe.bindings.contains_key("http"));
+}
+
+#[test]
+fn message_schemas_are_shared_with_openapi() {
+    let asyncapi = asyncapi_get_spec![endpoints::ticks, endpoints::last_tick];
+    let openapi = openapi_get_spec![endpoints::ticks, endpoints::last_tick];
+
+    let asyncapi_schemas = &asyncapi.components.as_ref().unwrap().schemas;
+    let openapi_schemas = &openapi.components.as_ref().unwrap().schemas;
+    assert!(asyncapi_schemas.contains_key("Tick"));
+    assert_eq!(asyncapi_schemas["Tick"], openapi_schemas["Tick"]);
+}
+
+#[test]
+fn event_stream_response_documents_events() {
+    let spec = openapi_get_spec![endpoints::ticks];
+
+    let operation = spec.paths["/ticks/{interval}"].get.as_ref().unwrap();
+    let response = match &operation.responses.responses["200"] {
+        okapi::openapi3::RefOr::Object(response) => response,
+        okapi::openapi3::RefOr::Ref(_) => panic!("Expected response object"),
+    };
+    let item_schema = &response.content["text/event-stream"].extensions["x-itemSchema"];
+    assert_eq!(
+        item_schema["properties"]["data"]["contentSchema"]["$ref"],
+        "#/components/schemas/Tick"
+    );
+    assert_eq!(
+        item_schema["properties"]["event"]["enum"],
+        serde_json::json!(["tick", "reset"])
+    );
+    assert_eq!(
+        item_schema["properties"]["id"]["description"],
+        "Sequence number, resume with `Last-Event-ID`."
+    );
+}
+
+#[test]
+fn event_stream_is_detected_through_aliases() {
+    let spec = asyncapi_get_spec![endpoints::alarms];
+
+    let subscribe = spec.channels["/alarms"].subscribe.as_ref().unwrap();
+    assert!(subscribe.bindings.contains_key("http"));
+    assert!(spec.channels["/alarms"].bindings.is_empty());
+}
+
+#[test]
+fn routes_with_the_same_path_are_one_channel() {
+    let spec = asyncapi_get_spec![endpoints::alarms, endpoints::set_alarm];
+
+    assert_eq!(spec.channels.len(), 1);
+    let channel = &spec.channels["/alarms"];
+    assert_eq!(channel.description.as_deref(), Some("Stream of alarms"));
+    assert!(channel.subscribe.is_some());
+    let publish = channel.publish.as_ref().unwrap();
+    assert_eq!(publish.description.as_deref(), Some("Sets an alarm"));
+}
+
+#[test]
+#[should_panic(expected = "Multiple routes send or receive messages on the channel `/alarms`.")]
+fn routes_sending_on_the_same_channel() {
+    asyncapi_get_spec![endpoints::alarms, endpoints::all_alarms];
+}
+
+#[test]
+fn both_specs_from_one_pass() {
+    let settings = rocket_okapi::settings::OpenApiSettings::new();
+    let (openapi, asyncapi) =
+        rocket_okapi::openapi_and_asyncapi_spec![endpoints::alarms, endpoints::set_alarm](
+            &settings,
+        );
+    assert_eq!(
+        serde_json::to_value(&openapi).unwrap(),
+        serde_json::to_value(openapi_get_spec![endpoints::alarms, endpoints::set_alarm]).unwrap()
+    );
+    assert_eq!(
+        serde_json::to_value(asyncapi.unwrap()).unwrap(),
+        serde_json::to_value(asyncapi_get_spec![endpoints::alarms, endpoints::set_alarm]).unwrap()
+    );
+    // Without channels there is no AsyncAPI document.
+    let (_, asyncapi) = rocket_okapi::openapi_and_asyncapi_spec![endpoints::last_tick](&settings);
+    assert!(asyncapi.is_none());
+}
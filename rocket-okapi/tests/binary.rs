@@ -0,0 +1,148 @@
+//! Test the documentation of binary request bodies and responses
+
+use rocket_okapi::openapi_get_spec;
+use rocket_okapi::settings::OpenApiSettings;
+use serde_json::{json, Value};
+
+// These functions are never actually called.
+#[allow(unused)]
+mod endpoints {
+    use rocket::fs::NamedFile;
+    use rocket::response::stream::{Event, EventStream};
+    use rocket::{get, post};
+    use rocket_okapi::openapi;
+    use rocket_okapi::response::Attachment;
+
+    #[openapi]
+    #[post("/blob", data = "<blob>")]
+    pub fn upload(blob: Vec<u8>) -> Vec<u8> {
+        blob
+    }
+
+    #[openapi]
+    #[get("/favicon.ico")]
+    pub async fn favicon() -> Option<NamedFile> {
+        None
+    }
+
+    pub type Icon = Result<NamedFile, std::io::Error>;
+
+    #[openapi]
+    #[get("/icon.png")]
+    pub async fn icon() -> Icon {
+        unimplemented!()
+    }
+
+    #[openapi]
+    #[get("/robots.txt")]
+    pub fn robots() -> &'static str {
+        "User-agent: *"
+    }
+
+    #[openapi]
+    #[get("/events")]
+    pub fn events() -> EventStream<impl rocket::futures::Stream<Item = Event>> {
+        EventStream! {
+            yield Event::data("tick");
+        }
+    }
+
+    #[openapi]
+    #[get("/export")]
+    pub fn export() -> Attachment<Vec<u8>> {
+        Attachment::new("export.bin", Vec::new())
+    }
+}
+
+fn spec_json(settings: OpenApiSettings) -> Value {
+    let spec = openapi_get_spec![
+        settings: endpoints::upload,
+        endpoints::favicon,
+        endpoints::icon,
+        endpoints::robots,
+        endpoints::events,
+        endpoints::export
+    ];
+    serde_json::to_value(spec).unwrap()
+}
+
+#[test]
+fn bytes_are_binary_strings() {
+    let spec = spec_json(OpenApiSettings::new());
+    let upload = &spec["paths"]["/blob"]["post"];
+    let binary = json!({ "type": "string", "format": "binary" });
+    assert_eq!(
+        upload["requestBody"]["content"]["application/octet-stream"]["schema"],
+        binary
+    );
+    assert_eq!(
+        upload["responses"]["200"]["content"]["application/octet-stream"]["schema"],
+        binary
+    );
+}
+
+#[test]
+fn bytes_use_content_media_type_for_openapi_3_1() {
+    let settings = OpenApiSettings {
+        openapi_version: "3.1.0".to_owned(),
+        ..OpenApiSettings::new()
+    };
+    let spec = spec_json(settings);
+    let schema = &spec["paths"]["/blob"]["post"]["requestBody"]["content"]
+        ["application/octet-stream"]["schema"];
+    assert_eq!(
+        schema,
+        &json!({ "type": "string", "contentMediaType": "application/octet-stream" })
+    );
+}
+
+#[test]
+fn named_file_content_type_from_extension() {
+    let spec = spec_json(OpenApiSettings::new());
+    let content = spec["paths"]["/favicon.ico"]["get"]["responses"]["200"]["content"]
+        .as_object()
+        .unwrap()
+        .clone();
+    assert_eq!(content.keys().collect::<Vec<_>>(), ["image/x-icon"]);
+    // `NamedFile` is detected through its `OpenApiResponderInner` implementation, so also in an
+    // alias.
+    let content = &spec["paths"]["/icon.png"]["get"]["responses"]["200"]["content"];
+    assert!(content.get("image/png").is_some());
+    // Other responders keep their content type.
+    let content = &spec["paths"]["/robots.txt"]["get"]["responses"]["200"]["content"];
+    assert!(content.get("text/plain").is_some());
+}
+
+#[test]
+fn event_stream_is_binary() {
+    let spec = spec_json(OpenApiSettings::new());
+    let content = &spec["paths"]["/events"]["get"]["responses"]["200"]["content"];
+    assert_eq!(
+        content["text/event-stream"]["schema"],
+        json!({ "type": "string", "format": "binary" })
+    );
+}
+
+#[test]
+fn attachment_documents_content_disposition() {
+    let spec = spec_json(OpenApiSettings::new());
+    let response = &spec["paths"]["/export"]["get"]["responses"]["200"];
+    assert_eq!(
+        response["headers"]["Content-Disposition"]["schema"]["type"],
+        "string"
+    );
+    assert!(response["content"]["application/octet-stream"].is_object());
+}
+
+#[test]
+fn attachment_sets_content_disposition() {
+    use rocket::local::blocking::Client;
+
+    let rocket = rocket::build().mount("/", rocket::routes![endpoints::export]);
+    let client = Client::untracked(rocket).unwrap();
+    let response = client.get("/export").dispatch();
+    assert_eq!(
+        response.headers().get_one("Content-Disposition"),
+        Some("attachment; filename=\"export.bin\"")
+    );
+}
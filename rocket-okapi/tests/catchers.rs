@@ -0,0 +1,229 @@
+//! Test the documentation of Rocket catchers
+
+use rocket_okapi::openapi_get_spec;
+use rocket_okapi::settings::{CatcherResponses, OpenApiSettings};
+use serde_json::Value;
+
+// These functions are never actually called.
+#[allow(unused)]
+mod endpoints {
+    use rocket::http::Status;
+    use rocket::request::Request;
+    use rocket::response::status::BadRequest;
+    use rocket::response::{self, Redirect, Responder};
+    use rocket::{catch, delete, get, post, serde::json::Json};
+    use rocket_okapi::openapi;
+    use rocket_okapi::response::OpenApiErrorResponses;
+    use schemars::JsonSchema;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, JsonSchema)]
+    pub struct ErrorBody {
+        pub code: u16,
+        pub message: String,
+    }
+
+    /// The resource was not found.
+    #[openapi]
+    #[catch(404)]
+    pub fn not_found() -> Json<ErrorBody> {
+        Json(ErrorBody {
+            code: 404,
+            message: "Not Found".to_owned(),
+        })
+    }
+
+    /// Something went wrong.
+    #[openapi]
+    #[catch(default)]
+    pub fn default_catcher() -> Result<Json<ErrorBody>, String> {
+        Ok(Json(ErrorBody {
+            code: 500,
+            message: "Error".to_owned(),
+        }))
+    }
+
+    pub mod admin {
+        use rocket::catch;
+        use rocket_okapi::openapi;
+
+        /// No admin page here.
+        #[openapi]
+        #[catch(404)]
+        pub fn not_found() -> &'static str {
+            "Not Found"
+        }
+    }
+
+    #[openapi]
+    #[get("/user/<id>")]
+    pub fn get_user(id: u32) -> Option<Json<u32>> {
+        Some(Json(id))
+    }
+
+    #[openapi]
+    #[post("/user?<user>")]
+    pub fn create_user(user: u32) -> Result<Json<u32>, BadRequest<String>> {
+        Ok(Json(user))
+    }
+
+    #[derive(Serialize, JsonSchema)]
+    pub struct Locked {
+        pub until: String,
+    }
+
+    #[derive(Debug, OpenApiErrorResponses)]
+    pub enum DeleteUserError {
+        /// The user does not exist.
+        #[error_response(status = 404, schema = ErrorBody)]
+        NotFound,
+        /// The user is locked.
+        #[error_response(status = 423, schema = Locked)]
+        Locked,
+    }
+
+    impl<'r> Responder<'r, 'static> for DeleteUserError {
+        fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+            Err(Status::NotFound)
+        }
+    }
+
+    #[openapi]
+    #[delete("/user/<id>")]
+    pub fn delete_user(id: u32) -> Result<Json<u32>, DeleteUserError> {
+        Ok(Json(id))
+    }
+
+    #[openapi]
+    #[get("/home")]
+    pub fn home() -> Redirect {
+        Redirect::to("/")
+    }
+}
+
+fn spec_json(catcher_responses: CatcherResponses) -> Value {
+    let settings = OpenApiSettings {
+        catchers: rocket_okapi::openapi_catchers![endpoints::not_found, endpoints::default_catcher],
+        catcher_responses,
+        ..OpenApiSettings::new()
+    };
+    let spec = openapi_get_spec![
+        settings: endpoints::get_user,
+        endpoints::create_user,
+        endpoints::delete_user,
+        endpoints::home
+    ];
+    serde_json::to_value(spec).unwrap()
+}
+
+fn media_types(response: &Value) -> Vec<&String> {
+    let mut media_types: Vec<_> = response["content"].as_object().unwrap().keys().collect();
+    media_types.sort();
+    media_types
+}
+
+#[test]
+fn catcher_responses_are_components() {
+    let spec = spec_json(CatcherResponses::default());
+    let responses = &spec["components"]["responses"];
+    assert_eq!(
+        responses["not_found"]["description"],
+        "The resource was not found."
+    );
+    assert_eq!(
+        responses["not_found"]["content"]["application/json"]["schema"]["$ref"],
+        "#/components/schemas/ErrorBody"
+    );
+    assert_eq!(
+        responses["default_catcher"]["description"],
+        "Something went wrong."
+    );
+    // The responses of all responders of the return type are combined.
+    assert_eq!(
+        media_types(&responses["default_catcher"]),
+        ["application/json", "text/plain"]
+    );
+}
+
+#[test]
+fn catchers_with_the_same_name_are_module_qualified() {
+    let settings = OpenApiSettings {
+        catchers: rocket_okapi::openapi_catchers![
+            endpoints::not_found,
+            endpoints::admin::not_found
+        ],
+        ..OpenApiSettings::new()
+    };
+    let spec = openapi_get_spec![settings: endpoints::get_user];
+    let spec = serde_json::to_value(spec).unwrap();
+    let responses = spec["components"]["responses"].as_object().unwrap();
+    assert_eq!(
+        responses.keys().collect::<Vec<_>>(),
+        [
+            "catchers_endpoints_not_found",
+            "catchers_endpoints_admin_not_found"
+        ]
+    );
+    assert_eq!(
+        responses["catchers_endpoints_admin_not_found"]["description"],
+        "No admin page here."
+    );
+}
+
+#[test]
+fn matching_status_codes_reference_catchers() {
+    let spec = spec_json(CatcherResponses::MatchingStatusCodes);
+    let get_user = &spec["paths"]["/user/{id}"]["get"]["responses"];
+    assert_eq!(get_user["404"]["$ref"], "#/components/responses/not_found");
+    assert!(get_user["default"].is_null());
+    // The body of a `BadRequest<String>` is sent by the route, the catcher is not called.
+    let create_user = &spec["paths"]["/user"]["post"]["responses"];
+    assert!(create_user["400"].get("$ref").is_none());
+    assert_eq!(media_types(&create_user["400"]), ["text/plain"]);
+    assert!(create_user["200"].get("$ref").is_none());
+    assert!(create_user["404"].is_null());
+    // Errors without a catcher for their status code are handled by the default catcher. A
+    // response with its own description keeps it and gets the content of the catcher.
+    let home = &spec["paths"]["/home"]["get"]["responses"]["500"];
+    assert_eq!(home["description"], "The redirect URI is invalid.");
+    assert_eq!(media_types(home), ["application/json", "text/plain"]);
+}
+
+#[test]
+fn error_responses_with_content_are_kept() {
+    let spec = spec_json(CatcherResponses::MatchingStatusCodes);
+    let delete_user = &spec["paths"]["/user/{id}"]["delete"]["responses"];
+    assert_eq!(
+        delete_user["404"]["description"],
+        "The user does not exist."
+    );
+    assert_eq!(
+        delete_user["404"]["content"]["application/json"]["schema"]["$ref"],
+        "#/components/schemas/ErrorBody"
+    );
+    assert_eq!(
+        delete_user["423"]["content"]["application/json"]["schema"]["$ref"],
+        "#/components/schemas/Locked"
+    );
+}
+
+#[test]
+fn all_operations_get_catcher_responses() {
+    let spec = spec_json(CatcherResponses::AllOperations);
+    let create_user = &spec["paths"]["/user"]["post"]["responses"];
+    assert_eq!(
+        create_user["404"]["$ref"],
+        "#/components/responses/not_found"
+    );
+    assert_eq!(
+        create_user["default"]["$ref"],
+        "#/components/responses/default_catcher"
+    );
+    // Statuses that the operation documents are not replaced.
+    assert_eq!(media_types(&create_user["400"]), ["text/plain"]);
+    let delete_user = &spec["paths"]["/user/{id}"]["delete"]["responses"];
+    assert_eq!(
+        delete_user["404"]["content"]["application/json"]["schema"]["$ref"],
+        "#/components/schemas/ErrorBody"
+    );
+}
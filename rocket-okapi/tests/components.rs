@@ -0,0 +1,300 @@
+//! Test reusable responses, parameters, headers and request bodies
+
+use rocket_okapi::openapi_get_spec;
+use serde_json::{json, Value};
+
+// These functions are never actually called.
+#[allow(unused)]
+mod endpoints {
+    use okapi::openapi3::{
+        Object, Parameter, ParameterValue, Ref, RefOr, RequestBody, Response, Responses,
+    };
+    use rocket::data::{self, Data, FromData};
+    use rocket::http::Status;
+    use rocket::request::{self, FromRequest, Request};
+    use rocket::response::{self, Responder};
+    use rocket::{get, post};
+    use rocket_okapi::gen::OpenApiGenerator;
+    use rocket_okapi::openapi;
+    use rocket_okapi::request::{OpenApiFromData, OpenApiFromRequest, RequestHeaderInput};
+    use rocket_okapi::response::{Attachment, OpenApiResponderInner};
+    use rocket_okapi::util::string_header;
+
+    pub fn unauthorized(gen: &mut OpenApiGenerator) -> rocket_okapi::Result<Responses> {
+        let header = gen.register_header(
+            "WwwAuthenticate",
+            string_header("The authentication scheme.", true, None),
+        )?;
+        let response = Response {
+            description: "The API key is missing or invalid.".to_owned(),
+            headers: okapi::map! { "WWW-Authenticate".to_owned() => header },
+            ..Response::default()
+        };
+        let mut responses = Responses::default();
+        responses.responses.insert(
+            "401".to_owned(),
+            gen.register_response("Unauthorized", response)?,
+        );
+        Ok(responses)
+    }
+
+    pub struct ApiKey;
+
+    #[rocket::async_trait]
+    impl<'r> FromRequest<'r> for ApiKey {
+        type Error = ();
+
+        async fn from_request(_: &'r Request<'_>) -> request::Outcome<Self, ()> {
+            request::Outcome::Success(ApiKey)
+        }
+    }
+
+    impl<'r> OpenApiFromRequest<'r> for ApiKey {
+        fn from_request_input(
+            gen: &mut OpenApiGenerator,
+            _name: String,
+            _required: bool,
+        ) -> rocket_okapi::Result<RequestHeaderInput> {
+            let parameter = Parameter {
+                name: "X-Api-Key".to_owned(),
+                location: "header".to_owned(),
+                description: None,
+                required: true,
+                deprecated: false,
+                allow_empty_value: false,
+                value: ParameterValue::Schema {
+                    style: None,
+                    explode: None,
+                    allow_reserved: false,
+                    schema: schemars::json_schema!({ "type": "string" }),
+                    example: None,
+                    examples: None,
+                },
+                extensions: Object::default(),
+            };
+            Ok(RequestHeaderInput::Parameter(
+                gen.register_parameter("ApiKey", parameter)?,
+            ))
+        }
+
+        fn get_responses(gen: &mut OpenApiGenerator) -> rocket_okapi::Result<Responses> {
+            unauthorized(gen)
+        }
+    }
+
+    pub struct Unauthorized;
+
+    impl<'r> Responder<'r, 'static> for Unauthorized {
+        fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+            Err(Status::Unauthorized)
+        }
+    }
+
+    impl OpenApiResponderInner for Unauthorized {
+        fn responses(gen: &mut OpenApiGenerator) -> rocket_okapi::Result<Responses> {
+            unauthorized(gen)
+        }
+    }
+
+    /// Returns a `$ref` to the registered response instead of the response itself.
+    pub struct UnauthorizedRef;
+
+    impl<'r> Responder<'r, 'static> for UnauthorizedRef {
+        fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+            Err(Status::Unauthorized)
+        }
+    }
+
+    impl OpenApiResponderInner for UnauthorizedRef {
+        fn responses(gen: &mut OpenApiGenerator) -> rocket_okapi::Result<Responses> {
+            unauthorized(gen)?;
+            let mut responses = Responses::default();
+            responses.responses.insert(
+                "401".to_owned(),
+                RefOr::Ref(Ref {
+                    reference: "#/components/responses/Unauthorized".to_owned(),
+                }),
+            );
+            Ok(responses)
+        }
+    }
+
+    pub struct Document;
+
+    #[rocket::async_trait]
+    impl<'r> FromData<'r> for Document {
+        type Error = ();
+
+        async fn from_data(_: &'r Request<'_>, _: Data<'r>) -> data::Outcome<'r, Self> {
+            data::Outcome::Success(Document)
+        }
+    }
+
+    impl<'r> OpenApiFromData<'r> for Document {
+        fn request_body(gen: &mut OpenApiGenerator) -> rocket_okapi::Result<RequestBody> {
+            let request_body = RequestBody {
+                description: Some("A document.".to_owned()),
+                required: true,
+                ..RequestBody::default()
+            };
+            gen.register_request_body("Document", request_body)
+        }
+    }
+
+    #[openapi]
+    #[get("/document")]
+    pub fn get_document(key: ApiKey) -> Result<String, Unauthorized> {
+        unimplemented!()
+    }
+
+    #[openapi]
+    #[post("/document", data = "<document>")]
+    pub fn post_document(key: ApiKey, document: Document) {}
+
+    #[openapi]
+    #[get("/raw")]
+    pub fn raw() -> (rocket::http::ContentType, Unauthorized) {
+        unimplemented!()
+    }
+
+    #[openapi]
+    #[get("/export")]
+    pub fn export() -> Result<Vec<u8>, Attachment<Unauthorized>> {
+        unimplemented!()
+    }
+
+    #[openapi]
+    #[get("/raw_ref")]
+    pub fn raw_ref() -> (rocket::http::ContentType, UnauthorizedRef) {
+        unimplemented!()
+    }
+
+    #[openapi]
+    #[get("/export_ref")]
+    pub fn export_ref() -> Result<Vec<u8>, Attachment<UnauthorizedRef>> {
+        unimplemented!()
+    }
+}
+
+fn spec_json() -> Value {
+    let spec = openapi_get_spec![
+        endpoints::get_document,
+        endpoints::post_document,
+        endpoints::export,
+        endpoints::raw,
+        endpoints::raw_ref,
+        endpoints::export_ref
+    ];
+    serde_json::to_value(spec).unwrap()
+}
+
+#[test]
+fn registered_components_are_referenced() {
+    let spec = spec_json();
+    for method in ["get", "post"] {
+        let op = &spec["paths"]["/document"][method];
+        assert_eq!(
+            op["parameters"],
+            json!([{ "$ref": "#/components/parameters/ApiKey" }])
+        );
+        assert_eq!(
+            op["responses"]["401"],
+            json!({ "$ref": "#/components/responses/Unauthorized" })
+        );
+    }
+    assert_eq!(
+        spec["paths"]["/document"]["post"]["requestBody"],
+        json!({ "$ref": "#/components/requestBodies/Document" })
+    );
+
+    let components = &spec["components"];
+    assert_eq!(components["parameters"]["ApiKey"]["name"], "X-Api-Key");
+    assert_eq!(components["requestBodies"]["Document"]["required"], true);
+    assert_eq!(components["headers"]["WwwAuthenticate"]["required"], true);
+    let unauthorized = &components["responses"]["Unauthorized"];
+    assert_eq!(
+        unauthorized["description"],
+        "The API key is missing or invalid."
+    );
+    assert_eq!(
+        unauthorized["headers"]["WWW-Authenticate"],
+        json!({ "$ref": "#/components/headers/WwwAuthenticate" })
+    );
+    assert!(!spec.to_string().contains("x-okapi-component"));
+}
+
+#[test]
+fn changed_components_are_inlined() {
+    let spec = spec_json();
+    // `Attachment` adds a header to the successful responses, the `401` stays unchanged.
+    let responses = &spec["paths"]["/export"]["get"]["responses"];
+    assert_eq!(
+        responses["401"],
+        json!({ "$ref": "#/components/responses/Unauthorized" })
+    );
+    // The Content-Type of the response is changed, so it is a copy of the component.
+    let response = &spec["paths"]["/raw"]["get"]["responses"]["401"];
+    assert_eq!(
+        response["description"],
+        "The API key is missing or invalid."
+    );
+    assert!(response["content"]["*/*"].is_object());
+    assert_eq!(
+        response["headers"]["WWW-Authenticate"],
+        json!({ "$ref": "#/components/headers/WwwAuthenticate" })
+    );
+    assert!(response.get("x-okapi-component").is_none());
+}
+
+#[test]
+fn refs_to_registered_components_are_resolved() {
+    let spec = spec_json();
+    // The unchanged `$ref` is kept.
+    assert_eq!(
+        spec["paths"]["/export_ref"]["get"]["responses"]["401"],
+        json!({ "$ref": "#/components/responses/Unauthorized" })
+    );
+    // Changing the Content-Type resolves the `$ref` into a copy of the component.
+    let response = &spec["paths"]["/raw_ref"]["get"]["responses"]["401"];
+    assert_eq!(response, &spec["paths"]["/raw"]["get"]["responses"]["401"]);
+}
+
+#[test]
+fn unregistered_refs_can_not_be_changed() {
+    use okapi::openapi3::{Ref, RefOr, Responses};
+    use rocket_okapi::gen::OpenApiGenerator;
+    use rocket_okapi::settings::OpenApiSettings;
+
+    let gen = OpenApiGenerator::new(&OpenApiSettings::new());
+    let mut responses = Responses::default();
+    responses.responses.insert(
+        "401".to_owned(),
+        RefOr::Ref(Ref {
+            reference: "#/components/responses/Unknown".to_owned(),
+        }),
+    );
+    gen.resolve_response_refs(&mut responses);
+    let err = rocket_okapi::util::set_content_type(&mut responses, rocket::http::ContentType::Text)
+        .unwrap_err();
+    assert!(err.to_string().contains("`#/components/responses/Unknown`"));
+}
+
+#[test]
+fn conflicting_registrations_are_an_error() {
+    use okapi::openapi3::Response;
+    use rocket_okapi::gen::OpenApiGenerator;
+    use rocket_okapi::settings::OpenApiSettings;
+
+    let mut gen = OpenApiGenerator::new(&OpenApiSettings::new());
+    // Registering the same component again is fine.
+    endpoints::unauthorized(&mut gen).unwrap();
+    endpoints::unauthorized(&mut gen).unwrap();
+    let response = Response {
+        description: "Something else.".to_owned(),
+        ..Response::default()
+    };
+    let err = gen.register_response("Unauthorized", response).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("`components.responses` as `Unauthorized`"));
+}
@@ -0,0 +1,124 @@
+//! Test the `# Parameters`, `# Errors`, `# Responses` and `# Deprecated` sections of route docs
+
+use rocket_okapi::openapi_get_spec;
+use serde_json::Value;
+
+// These functions are never actually called.
+#[allow(unused)]
+mod endpoints {
+    use rocket::get;
+    use rocket::serde::json::Json;
+    use rocket_okapi::openapi;
+
+    /// # Get a pet
+    ///
+    /// Returns the name of the pet.
+    ///
+    /// # Parameters
+    ///
+    /// * `id` - The id of the pet.
+    /// * `lang` - The language of the name.
+    ///
+    /// # Responses
+    ///
+    /// * 200 - The name of the pet.
+    ///
+    /// # Errors
+    ///
+    /// * 404 - There is no pet with this id.
+    /// * 503 - The pet store is closed.
+    #[openapi(param(name = "lang", description = "An ISO 639-1 code."))]
+    #[get("/pets/<id>?<lang>")]
+    pub fn get_pet(id: u64, lang: Option<String>) -> Option<Json<String>> {
+        unimplemented!()
+    }
+
+    /// Returns the names of all pets.
+    ///
+    /// # Deprecated
+    ///
+    /// Use `/pets/<id>` instead.
+    #[openapi]
+    #[get("/pets")]
+    pub fn list_pets() -> Json<Vec<String>> {
+        unimplemented!()
+    }
+
+    /// Returns the names of all pet stores.
+    ///
+    /// # Errors
+    ///
+    /// Fails when the database of the pet stores is down.
+    #[openapi]
+    #[get("/stores")]
+    pub fn list_stores() -> Json<Vec<String>> {
+        unimplemented!()
+    }
+}
+
+fn spec_json() -> Value {
+    let spec = openapi_get_spec![
+        endpoints::get_pet,
+        endpoints::list_pets,
+        endpoints::list_stores
+    ];
+    serde_json::to_value(spec).unwrap()
+}
+
+#[test]
+fn sections_are_not_in_the_description() {
+    let spec = spec_json();
+    let op = &spec["paths"]["/pets/{id}"]["get"];
+    assert_eq!(op["summary"], "Get a pet");
+    assert_eq!(op["description"], "Returns the name of the pet.");
+    assert!(op.get("deprecated").is_none());
+}
+
+#[test]
+fn parameters_section() {
+    let spec = spec_json();
+    let parameters = &spec["paths"]["/pets/{id}"]["get"]["parameters"];
+    assert_eq!(parameters[0]["name"], "id");
+    assert_eq!(parameters[0]["description"], "The id of the pet.");
+    // The `param(...)` of the attribute takes precedence.
+    assert_eq!(parameters[1]["name"], "lang");
+    assert_eq!(parameters[1]["description"], "An ISO 639-1 code.");
+}
+
+#[test]
+fn responses_and_errors_sections() {
+    let spec = spec_json();
+    let responses = &spec["paths"]["/pets/{id}"]["get"]["responses"];
+    assert_eq!(responses["200"]["description"], "The name of the pet.");
+    assert!(responses["200"]["content"]["application/json"].is_object());
+    assert_eq!(
+        responses["404"]["description"],
+        "There is no pet with this id."
+    );
+    // Responses that are only documented are added.
+    assert_eq!(responses["503"]["description"], "The pet store is closed.");
+}
+
+#[test]
+fn deprecated_section() {
+    let spec = spec_json();
+    let op = &spec["paths"]["/pets"]["get"];
+    assert_eq!(op["deprecated"], true);
+    assert_eq!(
+        op["description"],
+        "Returns the names of all pets.\n\n**Deprecated:** Use `/pets/<id>` instead."
+    );
+}
+
+#[test]
+fn sections_without_items_stay_in_the_description() {
+    let spec = spec_json();
+    let op = &spec["paths"]["/stores"]["get"];
+    assert!(op.get("summary").is_none());
+    assert_eq!(
+        op["description"],
+        "Returns the names of all pet stores.\n\n# Errors\n\n\
+        Fails when the database of the pet stores is down."
+    );
+    assert_eq!(op["responses"].as_object().unwrap().len(), 1);
+}
@@ -0,0 +1,147 @@
+//! Test the documentation of error enums that derive `OpenApiErrorResponses`
+
+use rocket_okapi::openapi_get_spec;
+use serde_json::Value;
+
+// These functions are never actually called.
+#[allow(unused)]
+mod endpoints {
+    use rocket::http::Status;
+    use rocket::request::Request;
+    use rocket::response::{self, Responder};
+    use rocket::serde::json::Json;
+    use rocket::{get, post};
+    use rocket_okapi::openapi;
+    use rocket_okapi::response::OpenApiErrorResponses;
+    use schemars::JsonSchema;
+    use serde::Serialize;
+
+    #[derive(Serialize, JsonSchema)]
+    pub struct User {
+        pub name: String,
+    }
+
+    #[derive(Serialize, JsonSchema)]
+    pub struct ErrorMessage {
+        pub message: String,
+    }
+
+    #[derive(Serialize, JsonSchema)]
+    pub struct Conflict {
+        pub existing: User,
+    }
+
+    #[derive(Debug, OpenApiErrorResponses)]
+    #[error_response(schema = ErrorMessage)]
+    pub enum UserError {
+        /// The name is invalid.
+        #[error_response(status = 400)]
+        InvalidName,
+        /// The name is too long.
+        #[error_response(status = 400)]
+        NameTooLong,
+        /// The name is invalid.
+        #[error_response(status = 400)]
+        EmptyName,
+        /// The user does not exist.
+        #[error_response(status = 404, content_type = "text")]
+        NotFound,
+        /// The user already exists.
+        #[error_response(status = 409, schema = Conflict)]
+        Conflict,
+    }
+
+    impl<'r> Responder<'r, 'static> for UserError {
+        fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+            Err(Status::BadRequest)
+        }
+    }
+
+    #[derive(Debug, OpenApiErrorResponses)]
+    pub enum CreateUserError {
+        /// The user already exists.
+        #[error_response(status = 409, schema = Conflict)]
+        Exists,
+        /// The name is reserved.
+        #[error_response(status = 409, schema = ErrorMessage)]
+        Reserved,
+    }
+
+    impl<'r> Responder<'r, 'static> for CreateUserError {
+        fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+            Err(Status::Conflict)
+        }
+    }
+
+    #[openapi]
+    #[get("/user")]
+    pub fn get_user() -> Result<Json<User>, UserError> {
+        unimplemented!()
+    }
+
+    #[openapi]
+    #[post("/user")]
+    pub fn create_user() -> Result<Json<User>, CreateUserError> {
+        unimplemented!()
+    }
+}
+
+fn spec_json() -> Value {
+    let spec = openapi_get_spec![endpoints::get_user, endpoints::create_user];
+    serde_json::to_value(spec).unwrap()
+}
+
+#[test]
+fn every_variant_has_its_own_response() {
+    let spec = spec_json();
+    let responses = &spec["paths"]["/user"]["get"]["responses"];
+    assert_eq!(
+        responses.as_object().unwrap().keys().collect::<Vec<_>>(),
+        ["200", "400", "404", "409"]
+    );
+    assert_eq!(
+        responses["200"]["content"]["application/json"]["schema"]["$ref"],
+        "#/components/schemas/User"
+    );
+
+    // Variants with the same status code share one response, with every description and
+    // schema listed once.
+    let invalid = &responses["400"];
+    assert_eq!(
+        invalid["description"],
+        "- The name is invalid.\n- The name is too long."
+    );
+    assert_eq!(
+        invalid["content"]["application/json"]["schema"]["$ref"],
+        "#/components/schemas/ErrorMessage"
+    );
+    let not_found = &responses["404"];
+    assert_eq!(not_found["description"], "The user does not exist.");
+    assert!(not_found["content"]["text/plain; charset=utf-8"].is_object());
+    assert_eq!(
+        responses["409"]["content"]["application/json"]["schema"]["$ref"],
+        "#/components/schemas/Conflict"
+    );
+}
+
+#[test]
+fn variants_with_the_same_status_combine_their_schemas() {
+    let spec = spec_json();
+    let responses = &spec["paths"]["/user"]["post"]["responses"];
+    assert_eq!(
+        responses.as_object().unwrap().keys().collect::<Vec<_>>(),
+        ["200", "409"]
+    );
+    let conflict = &responses["409"];
+    assert_eq!(
+        conflict["description"],
+        "- The user already exists.\n- The name is reserved."
+    );
+    assert_eq!(
+        conflict["content"]["application/json"]["schema"]["anyOf"],
+        serde_json::json!([
+            { "$ref": "#/components/schemas/Conflict" },
+            { "$ref": "#/components/schemas/ErrorMessage" }
+        ])
+    );
+}
@@ -0,0 +1,145 @@
+//! Test examples of parameters, request bodies and responses
+
+use rocket_okapi::openapi_get_spec;
+use serde_json::{json, Value};
+
+// These functions are never actually called.
+#[allow(unused)]
+mod endpoints {
+    use rocket::form::FromForm;
+    use rocket::serde::json::Json;
+    use rocket::{get, post, put};
+    use rocket_okapi::example::OpenApiExample;
+    use rocket_okapi::{openapi, JsonSchema};
+    use serde::{Deserialize, Serialize};
+    use serde_json::{json, Value};
+
+    #[derive(Serialize, Deserialize, JsonSchema)]
+    #[schemars(example = Pet { name: "Rex".to_owned(), age: 3 })]
+    pub struct Pet {
+        pub name: String,
+        pub age: u8,
+    }
+
+    #[derive(Serialize, Deserialize, JsonSchema)]
+    pub struct NewPet {
+        pub name: String,
+    }
+
+    impl OpenApiExample for NewPet {
+        fn example() -> Value {
+            json!({ "name": "Felix" })
+        }
+    }
+
+    #[derive(FromForm, JsonSchema)]
+    pub struct Filter {
+        #[schemars(example = &"dog")]
+        pub kind: String,
+    }
+
+    #[openapi]
+    #[get("/pets?<filter..>")]
+    pub fn list_pets(filter: Filter) -> Json<Vec<Pet>> {
+        unimplemented!()
+    }
+
+    #[openapi]
+    #[get("/pets/<id>")]
+    pub fn get_pet(id: u64) -> Option<Json<Pet>> {
+        unimplemented!()
+    }
+
+    #[openapi(response_example(status = 200, value = json!({ "name": "Felix", "age": 0 })))]
+    #[post("/pets", data = "<pet>")]
+    pub fn create_pet(pet: Json<NewPet>) -> Json<Pet> {
+        unimplemented!()
+    }
+
+    #[openapi(request_example = NewPet { name: "Tom".to_owned() })]
+    #[put("/pets/<id>", data = "<pet>")]
+    pub fn update_pet(id: u64, pet: Json<NewPet>) -> Json<NewPet> {
+        unimplemented!()
+    }
+}
+
+fn spec_json() -> Value {
+    let spec = openapi_get_spec![
+        endpoints::list_pets,
+        endpoints::get_pet,
+        endpoints::create_pet,
+        endpoints::update_pet
+    ];
+    serde_json::to_value(spec).unwrap()
+}
+
+fn json_example(content: &Value) -> &Value {
+    &content["content"]["application/json"]["example"]
+}
+
+#[test]
+fn schema_examples() {
+    let spec = spec_json();
+    let pet = json!({ "name": "Rex", "age": 3 });
+    let get_pet = &spec["paths"]["/pets/{id}"]["get"];
+    assert_eq!(json_example(&get_pet["responses"]["200"]), &pet);
+    // The example of an array is an array with the example of its items.
+    let list_pets = &spec["paths"]["/pets"]["get"];
+    assert_eq!(json_example(&list_pets["responses"]["200"]), &json!([pet]));
+    let parameter = &list_pets["parameters"][0];
+    assert_eq!(parameter["name"], "kind");
+    assert_eq!(parameter["example"], "dog");
+    // Parameters without examples are left alone.
+    assert!(get_pet["parameters"][0].get("example").is_none());
+}
+
+#[test]
+fn trait_examples() {
+    let spec = spec_json();
+    let create_pet = &spec["paths"]["/pets"]["post"];
+    assert_eq!(
+        json_example(&create_pet["requestBody"]),
+        &json!({ "name": "Felix" })
+    );
+    // `Json<NewPet>` implements `OpenApiExample` through `NewPet`.
+    let update_pet = &spec["paths"]["/pets/{id}"]["put"];
+    assert_eq!(
+        json_example(&update_pet["responses"]["200"]),
+        &json!({ "name": "Felix" })
+    );
+}
+
+#[test]
+fn attribute_examples() {
+    let spec = spec_json();
+    // The attribute takes precedence over the schema and the `OpenApiExample` trait.
+    let create_pet = &spec["paths"]["/pets"]["post"];
+    assert_eq!(
+        json_example(&create_pet["responses"]["200"]),
+        &json!({ "name": "Felix", "age": 0 })
+    );
+    let update_pet = &spec["paths"]["/pets/{id}"]["put"];
+    assert_eq!(
+        json_example(&update_pet["requestBody"]),
+        &json!({ "name": "Tom" })
+    );
+}
+
+#[test]
+fn response_example_needs_content() {
+    use okapi::openapi3::{RefOr, Response, Responses};
+    use rocket_okapi::util::set_response_example;
+
+    let mut responses = Responses::default();
+    responses.responses.insert(
+        "204".to_owned(),
+        RefOr::Object(Response {
+            description: "No content.".to_owned(),
+            ..Response::default()
+        }),
+    );
+    let err = set_response_example(&mut responses, 204, json!({})).unwrap_err();
+    assert!(err.to_string().contains("because it has no content"));
+    let err = set_response_example(&mut responses, 404, json!({})).unwrap_err();
+    assert!(err.to_string().contains("because it is not documented"));
+}
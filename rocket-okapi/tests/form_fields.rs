@@ -0,0 +1,161 @@
+//! Test the documentation of forms that derive `FormSchema`
+
+use rocket_okapi::openapi_get_spec;
+use serde_json::Value;
+
+// These functions are never actually called.
+#[allow(unused)]
+mod endpoints {
+    use rocket::form::{Form, FromForm};
+    use rocket::fs::TempFile;
+    use rocket::{get, post};
+    use rocket_okapi::openapi;
+    use rocket_okapi::request::FormSchema;
+
+    /// The search criteria.
+    #[derive(FromForm, FormSchema)]
+    pub struct Search<'r> {
+        /// The text to search for.
+        #[field(name = uncased("q"), validate = len(1..50))]
+        pub query: &'r str,
+        #[field(validate = range(1..=100), default = 20)]
+        pub limit: i64,
+        #[field(validate = len(..=5).or_else(msg!("Too many numbers.")))]
+        #[field(validate = one_of(&[3, 5, 7]))]
+        pub numbers: Vec<u8>,
+        pub page: Option<u32>,
+        pub r#type: Option<String>,
+    }
+
+    #[derive(FromForm, FormSchema)]
+    pub struct Upload<'r> {
+        pub title: String,
+        #[field(validate = ext(rocket::http::ContentType::PNG))]
+        pub image: TempFile<'r>,
+        pub attachments: Vec<TempFile<'r>>,
+    }
+
+    #[openapi]
+    #[post("/upload", data = "<upload>")]
+    pub fn upload(upload: Form<Upload<'_>>) {}
+
+    #[openapi]
+    #[get("/search?<search..>")]
+    pub fn search(search: Search<'_>) {}
+
+    #[openapi]
+    #[post("/search", data = "<search>")]
+    pub fn post_search(search: Form<Search<'_>>) {}
+}
+
+fn spec_json() -> Value {
+    let spec = openapi_get_spec![endpoints::search, endpoints::post_search, endpoints::upload];
+    serde_json::to_value(spec).unwrap()
+}
+
+#[test]
+fn query_parameters_use_field_attributes() {
+    let spec = spec_json();
+    let parameters = spec["paths"]["/search"]["get"]["parameters"]
+        .as_array()
+        .unwrap()
+        .clone();
+    let parameter = |name: &str| {
+        parameters
+            .iter()
+            .find(|p| p["name"] == name)
+            .unwrap_or_else(|| panic!("parameter `{name}` is missing"))
+            .clone()
+    };
+    let query = parameter("q");
+    assert_eq!(query["required"], true);
+    assert_eq!(query["description"], "The text to search for.");
+    assert_eq!(query["schema"]["minLength"], 1);
+    assert_eq!(query["schema"]["maxLength"], 49);
+    let limit = parameter("limit");
+    assert_ne!(limit["required"], true);
+    assert_eq!(limit["schema"]["minimum"], 1);
+    assert_eq!(limit["schema"]["maximum"], 100);
+    let numbers = parameter("numbers");
+    assert_ne!(numbers["required"], true);
+    assert_eq!(numbers["schema"]["maxItems"], 5);
+    assert_eq!(
+        numbers["schema"]["contains"]["enum"],
+        serde_json::json!([3, 5, 7])
+    );
+    assert_ne!(parameter("page")["required"], true);
+    // Raw identifiers are named like Rocket does, without `r#`.
+    assert_ne!(parameter("type")["required"], true);
+    assert!(parameters.iter().all(|p| p["name"] != "query"));
+}
+
+#[test]
+fn form_schema_uses_field_attributes() {
+    let spec = spec_json();
+    let schema = &spec["components"]["schemas"]["Search"];
+    assert_eq!(schema["description"], "The search criteria.");
+    assert_eq!(schema["required"], serde_json::json!(["q"]));
+    assert_eq!(schema["properties"]["q"]["maxLength"], 49);
+}
+
+#[test]
+fn forms_accept_both_content_types() {
+    let spec = spec_json();
+    let content = &spec["paths"]["/search"]["post"]["requestBody"]["content"];
+    for content_type in ["application/x-www-form-urlencoded", "multipart/form-data"] {
+        assert_eq!(
+            content[content_type]["schema"]["$ref"],
+            "#/components/schemas/Search"
+        );
+    }
+}
+
+#[test]
+fn file_fields_are_binary() {
+    let spec = spec_json();
+    let content = &spec["paths"]["/upload"]["post"]["requestBody"]["content"];
+    // Files can not be uploaded with `application/x-www-form-urlencoded`.
+    assert!(content["application/x-www-form-urlencoded"].is_null());
+    let encoding = &content["multipart/form-data"]["encoding"];
+    assert_eq!(encoding["image"]["contentType"], "image/png");
+    assert_eq!(
+        encoding["attachments"]["contentType"],
+        "application/octet-stream"
+    );
+    assert!(encoding["title"].is_null());
+
+    let properties = &spec["components"]["schemas"]["Upload"]["properties"];
+    assert_eq!(properties["image"]["type"], "string");
+    assert_eq!(properties["image"]["format"], "binary");
+    assert_eq!(properties["attachments"]["type"], "array");
+    assert_eq!(properties["attachments"]["items"]["format"], "binary");
+    assert!(properties["image"].get("contentMediaType").is_some());
+    assert!(!spec.to_string().contains("x-okapi"));
+}
+
+#[test]
+fn file_fields_use_content_media_type_for_openapi_3_1() {
+    let settings = rocket_okapi::settings::OpenApiSettings {
+        openapi_version: "3.1.0".to_owned(),
+        ..rocket_okapi::settings::OpenApiSettings::new()
+    };
+    let spec = openapi_get_spec![settings: endpoints::upload];
+    let spec = serde_json::to_value(spec).unwrap();
+    let properties = &spec["components"]["schemas"]["Upload"]["properties"];
+    assert_eq!(
+        properties["image"],
+        serde_json::json!({ "type": "string", "contentMediaType": "image/png" })
+    );
+    assert_eq!(
+        properties["attachments"]["items"],
+        serde_json::json!({ "type": "string", "contentMediaType": "application/octet-stream" })
+    );
+    let encoding = &spec["paths"]["/upload"]["post"]["requestBody"]["content"]
+        ["multipart/form-data"]["encoding"];
+    assert_eq!(encoding["image"]["contentType"], "image/png");
+    assert_eq!(
+        encoding["attachments"]["contentType"],
+        "application/octet-stream"
+    );
+    assert!(!spec.to_string().contains("x-okapi"));
+}
@@ -0,0 +1,109 @@
+//! Test that the `format` of a route is used for the documented media types
+
+use rocket_okapi::openapi_get_spec;
+use rocket_okapi::settings::{OpenApiSettings, RankedRoutes};
+
+// These functions are never actually called.
+#[allow(unused)]
+mod endpoints {
+    use rocket::{get, post, serde::json::Json};
+    use rocket_okapi::openapi;
+
+    #[openapi]
+    #[post("/upload", format = "application/xml", data = "<body>")]
+    pub fn upload_xml(body: String) -> Json<()> {
+        Json(())
+    }
+
+    #[openapi]
+    #[post("/upload", format = "json", data = "<body>", rank = 2)]
+    pub fn upload_json(body: Json<Vec<String>>) -> Json<()> {
+        Json(())
+    }
+
+    #[openapi]
+    #[get("/report", format = "text/csv")]
+    pub fn report_csv() -> Option<String> {
+        None
+    }
+
+    #[openapi]
+    #[get("/status")]
+    pub fn status_json() -> Json<Vec<String>> {
+        Json(Vec::new())
+    }
+
+    #[openapi]
+    #[get("/status", rank = 2)]
+    pub fn status_text() -> String {
+        String::new()
+    }
+
+    #[openapi]
+    #[get("/report", format = "json", rank = 2)]
+    pub fn report_json() -> Option<Json<Vec<String>>> {
+        None
+    }
+}
+
+#[test]
+fn request_body_uses_format_of_payload_routes() {
+    let spec = openapi_get_spec![endpoints::upload_xml];
+
+    let operation = spec.paths["/upload"].post.as_ref().unwrap();
+    let request_body = operation.request_body.as_ref().unwrap();
+    let request_body = match request_body {
+        okapi::openapi3::RefOr::Object(request_body) => request_body,
+        okapi::openapi3::RefOr::Ref(_) => panic!("request body should not be a reference"),
+    };
+    let content_types: Vec<_> = request_body.content.keys().collect();
+    assert_eq!(content_types, ["application/xml"]);
+    // The response is not affected.
+    let response = serde_json::to_value(&operation.responses).unwrap();
+    assert!(response["200"]["content"]["application/json"].is_object());
+}
+
+#[test]
+fn response_uses_format_of_get_routes() {
+    let spec = openapi_get_spec![endpoints::report_csv];
+
+    let operation = spec.paths["/report"].get.as_ref().unwrap();
+    let responses = serde_json::to_value(&operation.responses).unwrap();
+    let content = responses["200"]["content"].as_object().unwrap();
+    assert_eq!(content.keys().collect::<Vec<_>>(), ["text/csv"]);
+    // Error responses have no content and are left untouched.
+    assert!(responses["404"]["content"].is_null());
+}
+
+#[test]
+fn routes_with_different_formats_are_merged() {
+    let spec = openapi_get_spec![
+        endpoints::upload_xml,
+        endpoints::upload_json,
+        endpoints::report_csv,
+        endpoints::report_json,
+    ];
+
+    let upload = serde_json::to_value(spec.paths["/upload"].post.as_ref().unwrap()).unwrap();
+    let content = upload["requestBody"]["content"].as_object().unwrap();
+    assert_eq!(content.len(), 2);
+    assert!(content.contains_key("application/xml"));
+    assert_eq!(content["application/json"]["schema"]["type"], "array");
+
+    let report = serde_json::to_value(spec.paths["/report"].get.as_ref().unwrap()).unwrap();
+    let content = report["responses"]["200"]["content"].as_object().unwrap();
+    assert_eq!(content.len(), 2);
+    assert!(content.contains_key("text/csv"));
+    assert!(content.contains_key("application/json"));
+}
+
+#[test]
+#[should_panic(expected = "Multiple routes are documented for `GET /status`.")]
+fn routes_without_format_are_ranked_routes() {
+    // The media types differ, but without a `format` these are ranked routes.
+    let settings = OpenApiSettings {
+        ranked_routes: RankedRoutes::Error,
+        ..OpenApiSettings::new()
+    };
+    let _spec = openapi_get_spec![settings: endpoints::status_json, endpoints::status_text];
+}
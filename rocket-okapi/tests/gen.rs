@@ -28,8 +28,9 @@ fn test_add_security_and_into_openapi_and_operation_id() {
         path: "/one".to_owned(),
         method: Method::Get,
         operation: op,
+        format: None,
     };
-    gen.add_operation(info);
+    gen.add_operation(info).unwrap();
 
     // Add another method to the same path
     let op2 = Operation {
@@ -40,7 +41,9 @@ fn test_add_security_and_into_openapi_and_operation_id() {
         path: "/one".to_owned(),
         method: Method::Post,
         operation: op2,
-    });
+        format: None,
+    })
+    .unwrap();
 
     let openapi = gen.into_openapi();
     // Paths should contain the route
@@ -62,5 +65,131 @@ fn test_json_schema_and_schema_generator_methods() {
     let _refsch = gen.json_schema_no_ref::<i32>();
     // schema_generator getter
     let _sg = gen.schema_generator();
-    assert!(!_sg.definitions().is_empty() || true);
+    // `i32` is not a definition.
+    assert!(_sg.definitions().is_empty());
+}
+
+#[test]
+fn test_into_openapi_applies_settings() {
+    use okapi::openapi3::{Info, Server, Tag};
+
+    let settings = OpenApiSettings {
+        info: Some(Info {
+            title: "My API".to_owned(),
+            version: "1.2.3".to_owned(),
+            ..Info::default()
+        }),
+        servers: vec![Server {
+            url: "https://example.com/".to_owned(),
+            ..Server::default()
+        }],
+        tags: vec![Tag {
+            name: "Users".to_owned(),
+            description: Some("Everything about users.".to_owned()),
+            ..Tag::default()
+        }],
+        security: vec![okapi::map! { "myscheme".to_owned() => vec![] }],
+        openapi_version: "3.0.3".to_owned(),
+        extensions: okapi::map! { "x-audience".to_owned() => serde_json::json!("public") },
+        ..OpenApiSettings::new()
+    };
+    let mut gen = OpenApiGenerator::new(&settings);
+    gen.set_info(Info {
+        title: "package".to_owned(),
+        ..Info::default()
+    });
+
+    let openapi = gen.into_openapi();
+    assert_eq!(openapi.openapi, "3.0.3");
+    // The `Info` from the settings takes precedence over the package information.
+    assert_eq!(openapi.info.title, "My API");
+    assert_eq!(openapi.servers[0].url, "https://example.com/");
+    assert_eq!(openapi.tags[0].name, "Users");
+    assert!(openapi.security[0].contains_key("myscheme"));
+    assert_eq!(openapi.extensions["x-audience"], "public");
+}
+
+#[test]
+fn test_into_openapi_uses_package_info_by_default() {
+    let mut gen = OpenApiGenerator::new(&OpenApiSettings::new());
+    gen.set_info(okapi::openapi3::Info {
+        title: "package".to_owned(),
+        ..Default::default()
+    });
+
+    let openapi = gen.into_openapi();
+    assert_eq!(openapi.openapi, "3.0.0");
+    assert_eq!(openapi.info.title, "package");
+}
+
+#[test]
+fn test_into_openapi_sets_default_descriptions() {
+    use okapi::openapi3::{RefOr, Response};
+
+    let mut gen = OpenApiGenerator::new(&OpenApiSettings::new());
+    let mut responses = Responses::default();
+    for status in ["303", "404", "4XX", "default"] {
+        responses
+            .responses
+            .insert(status.to_owned(), Response::default().into());
+    }
+    responses.responses.insert(
+        "200".to_owned(),
+        Response {
+            description: "The user.".to_owned(),
+            ..Response::default()
+        }
+        .into(),
+    );
+    gen.add_operation(OperationInfo {
+        path: "/user".to_owned(),
+        method: Method::Get,
+        operation: Operation {
+            responses,
+            ..Operation::default()
+        },
+        format: None,
+    })
+    .unwrap();
+
+    let openapi = gen.into_openapi();
+    let responses = &openapi.paths["/user"].get.as_ref().unwrap().responses;
+    let description = |status: &str| match &responses.responses[status] {
+        RefOr::Object(response) => response.description.clone(),
+        RefOr::Ref(_) => unreachable!(),
+    };
+    assert_eq!(description("200"), "The user.");
+    assert_eq!(description("303"), "See Other");
+    assert_eq!(description("404"), "Not Found");
+    assert_eq!(description("4XX"), "Client error");
+    assert_eq!(description("default"), "Default response");
+}
+
+#[test]
+fn test_openapi_version_selects_schema_dialect() {
+    #[derive(rocket_okapi::JsonSchema)]
+    struct Pet {
+        #[allow(dead_code)]
+        age: Option<u32>,
+    }
+
+    let age_schema = |openapi_version: &str| {
+        let settings = OpenApiSettings {
+            openapi_version: openapi_version.to_owned(),
+            ..OpenApiSettings::new()
+        };
+        let mut gen = OpenApiGenerator::new(&settings);
+        gen.json_schema::<Pet>();
+        let components = gen.into_openapi().components.unwrap();
+        serde_json::to_value(&components.schemas["Pet"]).unwrap()["properties"]["age"].clone()
+    };
+    assert_eq!(
+        age_schema("3.0.3"),
+        serde_json::json!({ "type": "integer", "format": "uint32", "minimum": 0, "nullable": true })
+    );
+    // OpenAPI 3.1 uses JSON Schema 2020-12, which has no `nullable`.
+    assert_eq!(
+        age_schema("3.1.0"),
+        serde_json::json!({ "type": ["integer", "null"], "format": "uint32", "minimum": 0 })
+    );
 }
@@ -0,0 +1,88 @@
+//! Test the `operationId` strategies and the detection of duplicate ids
+
+use rocket_okapi::openapi_get_spec;
+use rocket_okapi::settings::{OpenApiSettings, OperationIdStrategy};
+use std::sync::Arc;
+
+// These functions are never actually called.
+#[allow(unused)]
+mod endpoints {
+    pub mod post {
+        use rocket::{post, serde::json::Json};
+        use rocket_okapi::openapi;
+
+        #[openapi]
+        #[post("/posts/<id>")]
+        pub fn create_post(id: u32) -> Json<()> {
+            Json(())
+        }
+    }
+
+    use rocket::{get, serde::json::Json};
+    use rocket_okapi::openapi;
+
+    #[openapi(operation_id = "endpoints_post_create_post")]
+    #[get("/drafts")]
+    pub fn drafts() -> Json<()> {
+        Json(())
+    }
+}
+
+fn operation_id_with(strategy: OperationIdStrategy) -> String {
+    let settings = OpenApiSettings {
+        operation_id_strategy: strategy,
+        ..OpenApiSettings::new()
+    };
+    let spec = openapi_get_spec![settings: endpoints::post::create_post];
+    let operation = spec.paths["/posts/{id}"].post.as_ref().unwrap();
+    operation.operation_id.clone().unwrap()
+}
+
+#[test]
+fn operation_id_strategies() {
+    assert_eq!(
+        operation_id_with(OperationIdStrategy::default()),
+        "endpoints_post_create_post"
+    );
+    assert_eq!(
+        operation_id_with(OperationIdStrategy::FunctionName),
+        "create_post"
+    );
+    assert_eq!(
+        operation_id_with(OperationIdStrategy::CamelCase),
+        "endpointsPostCreatePost"
+    );
+    assert_eq!(
+        operation_id_with(OperationIdStrategy::MethodAndPath),
+        "post_posts_id"
+    );
+    assert_eq!(
+        operation_id_with(OperationIdStrategy::Custom(Arc::new(|input| format!(
+            "{}_v1",
+            input.function_name
+        )))),
+        "create_post_v1"
+    );
+}
+
+#[test]
+fn explicit_operation_id_ignores_strategy() {
+    let settings = OpenApiSettings {
+        operation_id_strategy: OperationIdStrategy::MethodAndPath,
+        ..OpenApiSettings::new()
+    };
+    let spec = openapi_get_spec![settings: endpoints::drafts];
+    let operation = spec.paths["/drafts"].get.as_ref().unwrap();
+    assert_eq!(
+        operation.operation_id.as_deref(),
+        Some("endpoints_post_create_post")
+    );
+}
+
+#[test]
+#[should_panic(
+    expected = "The operationId `endpoints_post_create_post` is used by both `POST /posts/{id}` and `GET /drafts`."
+)]
+fn duplicate_operation_ids_are_an_error() {
+    let _spec = openapi_get_spec![endpoints::post::create_post, endpoints::drafts];
+}
@@ -0,0 +1,144 @@
+//! Test the documentation of parameters with doc comments and `param(...)`
+
+use rocket_okapi::openapi_get_spec;
+use serde_json::{json, Value};
+
+// These functions are never actually called.
+#[allow(unused)]
+mod endpoints {
+    use okapi::openapi3::{Object, Parameter, ParameterValue};
+    use rocket::form::FromForm;
+    use rocket::get;
+    use rocket::request::{self, FromRequest, Request};
+    use rocket_okapi::gen::OpenApiGenerator;
+    use rocket_okapi::request::{OpenApiFromRequest, RequestHeaderInput};
+    use rocket_okapi::{openapi, JsonSchema};
+
+    pub struct ApiKey;
+
+    #[rocket::async_trait]
+    impl<'r> FromRequest<'r> for ApiKey {
+        type Error = ();
+
+        async fn from_request(_: &'r Request<'_>) -> request::Outcome<Self, ()> {
+            request::Outcome::Success(ApiKey)
+        }
+    }
+
+    impl<'r> OpenApiFromRequest<'r> for ApiKey {
+        fn from_request_input(
+            _gen: &mut OpenApiGenerator,
+            _name: String,
+            _required: bool,
+        ) -> rocket_okapi::Result<RequestHeaderInput> {
+            Ok(RequestHeaderInput::Parameter(Parameter {
+                name: "X-Api-Key".to_owned(),
+                location: "header".to_owned(),
+                description: None,
+                required: true,
+                deprecated: false,
+                allow_empty_value: false,
+                value: ParameterValue::Schema {
+                    style: None,
+                    explode: None,
+                    allow_reserved: false,
+                    schema: schemars::json_schema!({ "type": "string" }),
+                    example: None,
+                    examples: None,
+                },
+                extensions: Object::default(),
+            }))
+        }
+    }
+
+    #[derive(FromForm, JsonSchema)]
+    pub struct Filter {
+        pub kind: Option<String>,
+        pub age: Option<u8>,
+    }
+
+    #[openapi(
+        param(name = "id", example = 42),
+        param(name = "kind", description = "The kind of pet.", deprecated),
+        param(name = "key", description = "The API key of the owner.")
+    )]
+    #[get("/users/<id>/pets?<filter..>")]
+    pub fn get_pets(
+        /// The id of the owner.
+        id: u64,
+        key: ApiKey,
+        filter: Filter,
+    ) {
+    }
+
+    #[openapi(param(name = "X-Api-Key", example = "secret"))]
+    #[get("/search?<q>")]
+    pub fn search(
+        /// # Query
+        /// The text to search for.
+        q: String,
+        key: ApiKey,
+    ) {
+    }
+
+    // A typo in the name of the header of the request guard.
+    #[openapi(param(name = "X-Api-Token", example = "secret"))]
+    #[get("/owners")]
+    pub fn owners(key: ApiKey) {}
+}
+
+fn spec_json() -> Value {
+    let spec = openapi_get_spec![endpoints::get_pets, endpoints::search];
+    serde_json::to_value(spec).unwrap()
+}
+
+fn parameter<'a>(op: &'a Value, name: &str) -> &'a Value {
+    op["parameters"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|parameter| parameter["name"] == name)
+        .unwrap()
+}
+
+#[test]
+fn path_parameter_docs() {
+    let spec = spec_json();
+    let id = parameter(&spec["paths"]["/users/{id}/pets"]["get"], "id");
+    assert_eq!(id["description"], "The id of the owner.");
+    assert_eq!(id["example"], 42);
+    assert!(id.get("deprecated").is_none());
+}
+
+#[test]
+fn query_parameter_docs() {
+    let spec = spec_json();
+    let op = &spec["paths"]["/users/{id}/pets"]["get"];
+    let kind = parameter(op, "kind");
+    assert_eq!(kind["description"], "The kind of pet.");
+    assert_eq!(kind["deprecated"], true);
+    assert!(parameter(op, "age").get("description").is_none());
+    let q = parameter(&spec["paths"]["/search"]["get"], "q");
+    assert_eq!(q["description"], "Query\n\nThe text to search for.");
+}
+
+#[test]
+fn request_guard_parameter_docs() {
+    let spec = spec_json();
+    // By the name of the argument.
+    let key = parameter(&spec["paths"]["/users/{id}/pets"]["get"], "X-Api-Key");
+    assert_eq!(key["description"], "The API key of the owner.");
+    assert!(key.get("example").is_none());
+    // By the name of the parameter.
+    let key = parameter(&spec["paths"]["/search"]["get"], "X-Api-Key");
+    assert!(key.get("description").is_none());
+    assert_eq!(key["example"], json!("secret"));
+}
+
+#[test]
+#[should_panic(expected = "X-Api-Token\\\")` of `owners` does not match any argument")]
+fn unmatched_parameter_docs_are_an_error() {
+    // Names that are not arguments of routes with request guards or query parameters are checked
+    // when the specification is generated. For other routes it is a compile error.
+    openapi_get_spec![endpoints::owners];
+}
@@ -0,0 +1,130 @@
+//! Test how routes with the same path and method but a different rank are documented
+
+use rocket_okapi::openapi_get_spec;
+use rocket_okapi::settings::{OpenApiSettings, RankedRoutes};
+
+// These functions are never actually called.
+#[allow(unused)]
+mod endpoints {
+    use rocket::request::{FromRequest, Outcome, Request};
+    use rocket::{get, serde::json::Json};
+    use rocket_okapi::gen::OpenApiGenerator;
+    use rocket_okapi::okapi::openapi3::{
+        Object, SecurityRequirement, SecurityScheme, SecuritySchemeData,
+    };
+    use rocket_okapi::openapi;
+    use rocket_okapi::request::{OpenApiFromRequest, RequestHeaderInput};
+
+    pub struct ApiKey;
+
+    #[rocket::async_trait]
+    impl<'r> FromRequest<'r> for ApiKey {
+        type Error = ();
+        async fn from_request(_request: &'r Request<'_>) -> Outcome<Self, ()> {
+            Outcome::Success(ApiKey)
+        }
+    }
+
+    impl<'r> OpenApiFromRequest<'r> for ApiKey {
+        fn from_request_input(
+            _gen: &mut OpenApiGenerator,
+            _name: String,
+            _required: bool,
+        ) -> rocket_okapi::Result<RequestHeaderInput> {
+            let scheme = SecurityScheme {
+                description: None,
+                data: SecuritySchemeData::ApiKey {
+                    name: "x-api-key".to_owned(),
+                    location: "header".to_owned(),
+                },
+                extensions: Object::default(),
+            };
+            let mut requirement = SecurityRequirement::new();
+            requirement.insert("ApiKeyAuth".to_owned(), Vec::new());
+            Ok(RequestHeaderInput::Security(
+                "ApiKeyAuth".to_owned(),
+                scheme,
+                requirement,
+            ))
+        }
+    }
+
+    /// Find a user by id.
+    #[openapi]
+    #[get("/user?<id>")]
+    pub fn user_by_id(id: u32) -> Json<u32> {
+        Json(id)
+    }
+
+    /// Find a user by name.
+    #[openapi]
+    #[get("/user?<name>", rank = 2)]
+    pub fn user_by_name(name: String) -> Json<String> {
+        Json(name)
+    }
+
+    /// List all users.
+    #[openapi]
+    #[get("/users")]
+    pub fn users(key: ApiKey) -> Json<Vec<u32>> {
+        Json(Vec::new())
+    }
+
+    /// List the public users.
+    #[openapi]
+    #[get("/users", rank = 2)]
+    pub fn public_users() -> Json<Vec<u32>> {
+        Json(Vec::new())
+    }
+}
+
+#[test]
+fn ranked_routes_are_merged_by_default() {
+    let spec = openapi_get_spec![endpoints::user_by_id, endpoints::user_by_name];
+
+    let operation = serde_json::to_value(spec.paths["/user"].get.as_ref().unwrap()).unwrap();
+    // The first route determines the description.
+    assert_eq!(operation["description"], "Find a user by id.");
+    // Both query parameters are documented, but neither is required anymore.
+    let parameters = operation["parameters"].as_array().unwrap();
+    assert_eq!(parameters.len(), 2);
+    assert!(parameters.iter().all(|p| p["required"] != true));
+    // The response is either of the two.
+    let schema = &operation["responses"]["200"]["content"]["application/json"]["schema"];
+    assert_eq!(schema["oneOf"].as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn ranked_routes_make_security_optional() {
+    let spec = openapi_get_spec![endpoints::users, endpoints::public_users];
+
+    let operation = serde_json::to_value(spec.paths["/users"].get.as_ref().unwrap()).unwrap();
+    // The security requirement is kept, and the route without one makes it optional.
+    assert_eq!(
+        operation["security"],
+        serde_json::json!([{ "ApiKeyAuth": [] }, {}])
+    );
+}
+
+#[test]
+fn ranked_routes_keep_first() {
+    let settings = OpenApiSettings {
+        ranked_routes: RankedRoutes::KeepFirst,
+        ..OpenApiSettings::new()
+    };
+    let spec = openapi_get_spec![settings: endpoints::user_by_id, endpoints::user_by_name];
+
+    let operation = spec.paths["/user"].get.as_ref().unwrap();
+    assert_eq!(operation.description.as_deref(), Some("Find a user by id."));
+    assert_eq!(operation.parameters.len(), 1);
+}
+
+#[test]
+#[should_panic(expected = "Multiple routes are documented for `GET /user`.")]
+fn ranked_routes_error() {
+    let settings = OpenApiSettings {
+        ranked_routes: RankedRoutes::Error,
+        ..OpenApiSettings::new()
+    };
+    let _spec = openapi_get_spec![settings: endpoints::user_by_id, endpoints::user_by_name];
+}
@@ -46,6 +46,115 @@ fn test_get_nested_form_parameters() {
     assert!(params.iter().any(|p| p.name == "id" && p.required));
 }
 
+#[derive(FromForm, JsonSchema)]
+#[allow(dead_code)]
+struct Paging {
+    #[field(default = 1)]
+    page: u32,
+    #[field(default = 20)]
+    per_page: u32,
+}
+
+#[derive(FromForm, JsonSchema)]
+#[allow(dead_code)]
+struct Filter {
+    id: i32,
+    name: Option<String>,
+    #[field(default = 0)]
+    offset: u32,
+}
+
+#[test]
+fn test_optional_query_parameters_not_required() {
+    #[allow(unused)]
+    mod endpoints {
+        use super::{Filter, Paging};
+        use rocket::get;
+        use rocket_okapi::openapi;
+
+        #[openapi]
+        #[get("/items?<item>&<limit>&<tags>&<paging>&<filter..>")]
+        pub fn items(
+            item: u32,
+            limit: Option<u32>,
+            tags: Vec<String>,
+            paging: Paging,
+            filter: Filter,
+        ) {
+        }
+    }
+
+    let spec = rocket_okapi::openapi_get_spec![endpoints::items];
+    let operation = spec.paths["/items"].get.as_ref().unwrap();
+    let required = |name: &str| {
+        operation
+            .parameters
+            .iter()
+            .find_map(|p| match p {
+                okapi::openapi3::RefOr::Object(p) if p.name == name => Some(p.required),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("parameter `{name}` is missing"))
+    };
+    assert!(required("item"));
+    assert!(!required("limit"));
+    assert!(!required("tags"));
+    // All fields of `Paging` have a default value.
+    assert!(!required("paging"));
+    // Flattened fields are only required when Rocket has no default value for them.
+    assert!(required("id"));
+    assert!(!required("name"));
+    assert!(!required("offset"));
+}
+
+#[derive(FromForm, JsonSchema)]
+#[allow(dead_code)]
+struct Search {
+    tags: Vec<String>,
+    range: Paging,
+    labels: std::collections::HashMap<String, String>,
+}
+
+#[test]
+fn test_query_parameter_styles() {
+    #[allow(unused)]
+    mod endpoints {
+        use super::{Paging, Search};
+        use rocket::get;
+        use rocket_okapi::openapi;
+
+        #[openapi]
+        #[get("/search?<ids>&<paging>&<search..>")]
+        pub fn search(ids: Vec<u32>, paging: Paging, search: Search) {}
+    }
+
+    let spec = rocket_okapi::openapi_get_spec![endpoints::search];
+    let operation = serde_json::to_value(spec.paths["/search"].get.as_ref().unwrap()).unwrap();
+    let parameter = |name: &str| {
+        operation["parameters"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|p| p["name"] == name)
+            .unwrap_or_else(|| panic!("parameter `{name}` is missing"))
+            .clone()
+    };
+    // `?ids=1&ids=2`
+    assert_eq!(parameter("ids")["style"], "form");
+    assert_eq!(parameter("ids")["explode"], true);
+    // `?paging[page]=1` or `?paging.page=1`
+    assert_eq!(parameter("paging")["style"], "deepObject");
+    assert_eq!(parameter("paging")["explode"], true);
+    // The fields of `search..` are flattened, but nested values keep their name.
+    assert_eq!(parameter("tags")["style"], "form");
+    assert_eq!(parameter("labels")["style"], "deepObject");
+    assert_eq!(parameter("range")["style"], "deepObject");
+    assert_eq!(
+        parameter("range")["schema"]["$ref"],
+        "#/components/schemas/Paging"
+    );
+}
+
 #[derive(JsonSchema, Serialize, Deserialize)]
 struct BodyShape {
     field: String,
@@ -80,6 +189,91 @@ fn test_openapi_from_data_form_and_option() {
     assert!(!rb2.required);
 }
 
+#[test]
+fn test_openapi_from_data_responses() {
+    let mut gen = OpenApiGenerator::new(&OpenApiSettings::new());
+    let r = <Json<BodyShape> as OpenApiFromData>::get_responses(&mut gen).unwrap();
+    for status in ["400", "413", "422"] {
+        assert!(r.responses.contains_key(status));
+    }
+    let r = <rocket::form::Form<MyForm> as OpenApiFromData>::get_responses(&mut gen).unwrap();
+    assert!(r.responses.contains_key("422"));
+    // Exceeding the limit of a strict data guard is a "400 Bad Request".
+    let r = <String as OpenApiFromData>::get_responses(&mut gen).unwrap();
+    assert!(r.responses.contains_key("400"));
+    assert!(!r.responses.contains_key("413"));
+    let r = <rocket::data::Capped<String> as OpenApiFromData>::get_responses(&mut gen).unwrap();
+    assert!(r.responses.contains_key("400"));
+    // Failures of optional data guards are handled by the handler.
+    let r = <Option<Json<BodyShape>> as OpenApiFromData>::get_responses(&mut gen).unwrap();
+    assert!(r.responses.is_empty());
+}
+
+#[test]
+fn test_data_guard_responses_are_added_to_operation() {
+    #[allow(unused)]
+    mod endpoints {
+        use super::BodyShape;
+        use rocket::{post, serde::json::Json};
+        use rocket_okapi::openapi;
+
+        #[openapi]
+        #[post("/shape", data = "<shape>")]
+        pub fn create_shape(shape: Json<BodyShape>) -> Json<BodyShape> {
+            shape
+        }
+    }
+
+    let spec = rocket_okapi::openapi_get_spec![endpoints::create_shape];
+    let responses = &spec.paths["/shape"].post.as_ref().unwrap().responses;
+    for status in ["200", "400", "413", "422"] {
+        assert!(responses.responses.contains_key(status));
+    }
+}
+
+#[test]
+fn test_openapi_from_data_body_limits() {
+    // Without `body_limits` no limits are documented.
+    let mut gen = OpenApiGenerator::new(&OpenApiSettings::new());
+    let rb = <String as OpenApiFromData>::request_body(&mut gen).unwrap();
+    assert!(!rb.extensions.contains_key("x-max-body-size"));
+
+    let figment = rocket::Config::figment().merge(("limits.json", "2 MiB"));
+    let settings = OpenApiSettings::new().with_body_limits_from(&figment);
+    let mut gen = OpenApiGenerator::new(&settings);
+    let rb = <Json<BodyShape> as OpenApiFromData>::request_body(&mut gen).unwrap();
+    assert_eq!(rb.extensions["x-max-body-size"], 2 * 1024 * 1024);
+    let r = <Json<BodyShape> as OpenApiFromData>::get_responses(&mut gen).unwrap();
+    let description = serde_json::to_value(&r.responses["413"]).unwrap()["description"].clone();
+    assert!(description
+        .as_str()
+        .unwrap()
+        .ends_with("The `json` limit is 2MiB."));
+
+    // Rocket's default limit for strings is 8 KiB.
+    let rb = <String as OpenApiFromData>::request_body(&mut gen).unwrap();
+    assert_eq!(rb.extensions["x-max-body-size"], 8 * 1024);
+    let schema = &rb.content["application/octet-stream"]
+        .schema
+        .as_ref()
+        .unwrap();
+    assert_eq!(schema.get("maxLength").unwrap(), 8 * 1024);
+
+    // Forms use the `form` limit for urlencoded bodies and `data-form` for multipart bodies.
+    let rb = <rocket::form::Form<MyForm> as OpenApiFromData>::request_body(&mut gen).unwrap();
+    assert!(!rb.extensions.contains_key("x-max-body-size"));
+    let form = &rb.content["application/x-www-form-urlencoded"];
+    assert_eq!(form.extensions["x-max-body-size"], 32 * 1024);
+    let multipart = &rb.content["multipart/form-data"];
+    assert_eq!(multipart.extensions["x-max-body-size"], 2 * 1024 * 1024);
+    let r = <rocket::form::Form<MyForm> as OpenApiFromData>::get_responses(&mut gen).unwrap();
+    let description = serde_json::to_value(&r.responses["413"]).unwrap()["description"].clone();
+    assert!(description
+        .as_str()
+        .unwrap()
+        .ends_with("The `form` limit is 32KiB. The `data-form` limit is 2MiB."));
+}
+
 #[test]
 fn test_openapi_from_request_accept_and_option() {
     let mut gen = OpenApiGenerator::new(&OpenApiSettings::new());
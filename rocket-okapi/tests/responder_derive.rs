@@ -0,0 +1,110 @@
+//! Test the documentation of responders that derive `OpenApiResponder`
+
+use rocket_okapi::openapi_get_spec;
+use serde_json::{json, Value};
+
+// These functions are never actually called.
+#[allow(unused)]
+mod endpoints {
+    use rocket::http::{ContentType, Header};
+    use rocket::serde::json::Json;
+    use rocket::{get, Responder};
+    use rocket_okapi::openapi;
+    use rocket_okapi::response::{OpenApiResponder, OpenApiResponderInner};
+    use schemars::JsonSchema;
+    use serde::Serialize;
+
+    #[derive(Serialize, JsonSchema)]
+    pub struct Message {
+        pub message: String,
+    }
+
+    #[derive(Responder, OpenApiResponder)]
+    pub enum ApiError {
+        /// The user does not exist.
+        #[response(status = 404, content_type = "json")]
+        NotFound(Json<Message>),
+        /// Too many requests were made.
+        #[response(status = 429)]
+        TooManyRequests {
+            message: String,
+            /// Seconds to wait before the next request.
+            retry_after: Header<'static>,
+        },
+    }
+
+    /// The rendered page.
+    #[derive(Responder, OpenApiResponder)]
+    #[response(content_type = "html")]
+    pub struct Page<R> {
+        inner: R,
+        kind: ContentType,
+        #[response(ignore)]
+        unused: u8,
+    }
+
+    #[openapi]
+    #[get("/user")]
+    pub fn user() -> Result<Json<Message>, ApiError> {
+        unimplemented!()
+    }
+
+    #[openapi]
+    #[get("/page")]
+    pub fn page() -> Page<String> {
+        unimplemented!()
+    }
+}
+
+fn spec_json() -> Value {
+    let spec = openapi_get_spec![endpoints::user, endpoints::page];
+    serde_json::to_value(spec).unwrap()
+}
+
+#[test]
+fn variants_have_their_own_status() {
+    let spec = spec_json();
+    let responses = &spec["paths"]["/user"]["get"]["responses"];
+    assert!(responses["200"]["content"]["application/json"].is_object());
+
+    let not_found = &responses["404"];
+    assert_eq!(not_found["description"], "The user does not exist.");
+    assert_eq!(
+        not_found["content"]["application/json"]["schema"]["$ref"],
+        "#/components/schemas/Message"
+    );
+
+    let too_many = &responses["429"];
+    assert_eq!(too_many["description"], "Too many requests were made.");
+    assert!(too_many["content"]["text/plain"].is_object());
+    assert_eq!(
+        too_many["headers"]["Retry-After"]["description"],
+        "Seconds to wait before the next request."
+    );
+}
+
+#[test]
+fn struct_uses_content_type_and_header_fields() {
+    let spec = spec_json();
+    let responses = &spec["paths"]["/page"]["get"]["responses"];
+    assert_eq!(
+        responses.as_object().unwrap().keys().collect::<Vec<_>>(),
+        ["200"]
+    );
+    let page = &responses["200"];
+    assert_eq!(page["description"], "The rendered page.");
+    assert_eq!(
+        page["content"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .collect::<Vec<_>>(),
+        ["text/html; charset=utf-8"]
+    );
+    assert_eq!(page["headers"]["Content-Type"]["required"], true);
+    assert_eq!(page["headers"].as_object().unwrap().len(), 1);
+    assert_eq!(
+        page["headers"]["Content-Type"]["schema"],
+        json!({ "type": "string" })
+    );
+}
@@ -228,6 +228,39 @@ fn test_raw_xml_content_wrapper() {
     }));
 }
 
+#[test]
+fn test_builtin_responder_headers() {
+    let mut gen = OpenApiGenerator::new(&OpenApiSettings::new());
+    let header = |responses: &Responses, status: &str, name: &str| {
+        let response = ensure_not_ref_for_tests(&responses.responses[status]);
+        match response.headers.get(name) {
+            Some(RefOr::Object(header)) => Some(header.required),
+            _ => None,
+        }
+    };
+
+    let r = <rocket::response::Redirect as OpenApiResponderInner>::responses(&mut gen).unwrap();
+    assert_eq!(header(&r, "303", "Location"), Some(true));
+    assert_eq!(header(&r, "500", "Location"), None);
+
+    let r =
+        <rocket::response::status::Created<String> as OpenApiResponderInner>::responses(&mut gen)
+            .unwrap();
+    assert_eq!(header(&r, "201", "Location"), Some(true));
+    assert_eq!(header(&r, "201", "ETag"), Some(false));
+
+    let r =
+        <rocket::response::Flash<String> as OpenApiResponderInner>::responses(&mut gen).unwrap();
+    assert_eq!(header(&r, "200", "Set-Cookie"), Some(true));
+
+    let r = <NamedFile as OpenApiResponderInner>::responses(&mut gen).unwrap();
+    assert_eq!(header(&r, "200", "Content-Type"), Some(false));
+
+    let r = <(rocket::http::ContentType, String) as OpenApiResponderInner>::responses(&mut gen)
+        .unwrap();
+    assert_eq!(header(&r, "200", "Content-Type"), Some(true));
+}
+
 // UTIL function tests
 #[test]
 fn test_set_content_type_and_default() {
@@ -259,3 +292,68 @@ fn ensure_not_ref_for_tests(
         RefOr::Object(o) => o.clone(),
     }
 }
+
+#[test]
+fn test_status_code_descriptions() {
+    use okapi::openapi3::Response;
+    use rocket_okapi::util::*;
+
+    let described = |description: &str| -> RefOr<Response> {
+        Response {
+            description: description.to_owned(),
+            ..Response::default()
+        }
+        .into()
+    };
+    let mut r = Responses::default();
+    r.responses.insert("200".to_owned(), described("The user."));
+    r.responses.insert("201".to_owned(), described("The user."));
+    set_status_code(&mut r, 404).unwrap();
+    // The same description is not repeated.
+    assert_eq!(
+        ensure_not_ref_for_tests(&r.responses["404"]).description,
+        "The user."
+    );
+
+    r.responses
+        .insert("400".to_owned(), described("Invalid user."));
+    set_status_code_with_description(&mut r, 422, "The user can not be processed.").unwrap();
+    assert_eq!(
+        ensure_not_ref_for_tests(&r.responses["422"]).description,
+        "The user can not be processed."
+    );
+    assert_eq!(default_description("422"), "Unprocessable Entity");
+}
+
+#[test]
+fn test_builtin_responder_descriptions() {
+    let mut gen = OpenApiGenerator::new(&OpenApiSettings::new());
+    let description = |responses: &Responses, status: &str| {
+        ensure_not_ref_for_tests(&responses.responses[status]).description
+    };
+    let r = <rocket::response::status::NoContent as OpenApiResponderInner>::responses(&mut gen)
+        .unwrap();
+    assert_eq!(
+        description(&r, "204"),
+        "The request succeeded without a response body."
+    );
+    let r =
+        <rocket::response::status::Created<String> as OpenApiResponderInner>::responses(&mut gen)
+            .unwrap();
+    assert_eq!(description(&r, "201"), "The resource was created.");
+    let r = <rocket::response::Redirect as OpenApiResponderInner>::responses(&mut gen).unwrap();
+    assert_eq!(
+        description(&r, "303"),
+        "The response can be found at the `Location`."
+    );
+    assert_eq!(description(&r, "500"), "The redirect URI is invalid.");
+    let r = <Option<String> as OpenApiResponderInner>::responses(&mut gen).unwrap();
+    assert_eq!(description(&r, "404"), "The resource was not found.");
+    // A `404` documented by the inner responder is kept.
+    let r =
+        <Option<rocket::response::status::NotFound<String>> as OpenApiResponderInner>::responses(
+            &mut gen,
+        )
+        .unwrap();
+    assert_ne!(description(&r, "404"), "The resource was not found.");
+}
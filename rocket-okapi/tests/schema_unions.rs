@@ -0,0 +1,149 @@
+//! Test how the schemas of several responders are combined
+
+use rocket_okapi::openapi_get_spec;
+use rocket_okapi::settings::{OpenApiSettings, SchemaUnions};
+use serde_json::{json, Value};
+
+// These functions are never actually called.
+#[allow(unused)]
+mod endpoints {
+    use rocket::get;
+    use rocket::serde::json::Json;
+    use rocket::Either;
+    use rocket_okapi::openapi;
+    use schemars::JsonSchema;
+    use serde::Serialize;
+
+    #[derive(Serialize, JsonSchema)]
+    #[serde(tag = "kind")]
+    pub enum Mammal {
+        Cat { lives: u8 },
+        Dog { good: bool },
+    }
+
+    #[derive(Serialize, JsonSchema)]
+    #[serde(tag = "kind")]
+    pub enum Bird {
+        Parrot { words: u32 },
+        Owl { wise: bool },
+    }
+
+    #[derive(Serialize, JsonSchema)]
+    pub struct Plant {
+        pub name: String,
+    }
+
+    #[derive(Serialize, JsonSchema)]
+    pub struct Tags(pub Vec<String>);
+
+    type Pet = Either<Json<Mammal>, Either<Json<Bird>, Json<Mammal>>>;
+
+    #[openapi]
+    #[get("/pet")]
+    pub fn pet() -> Pet {
+        unimplemented!()
+    }
+
+    #[openapi]
+    #[get("/thing")]
+    pub fn thing() -> Either<Json<Mammal>, Json<Plant>> {
+        unimplemented!()
+    }
+
+    #[openapi]
+    #[get("/plant")]
+    pub fn plant() -> Either<Json<Plant>, Json<Tags>> {
+        unimplemented!()
+    }
+
+    #[openapi]
+    #[get("/maybe_plant")]
+    pub fn maybe_plant() -> Json<Option<Plant>> {
+        unimplemented!()
+    }
+}
+
+fn spec_json(schema_unions: SchemaUnions) -> Value {
+    let settings = OpenApiSettings {
+        schema_unions,
+        // OpenAPI 3.1 documents `Option<T>` with an `anyOf`.
+        openapi_version: "3.1.0".to_owned(),
+        ..OpenApiSettings::new()
+    };
+    let spec = openapi_get_spec![
+        settings: endpoints::pet,
+        endpoints::thing,
+        endpoints::plant,
+        endpoints::maybe_plant
+    ];
+    let spec = serde_json::to_value(spec).unwrap();
+    assert!(!spec.to_string().contains("x-okapi"));
+    spec
+}
+
+fn schema<'a>(spec: &'a Value, path: &str) -> &'a Value {
+    &spec["paths"][path]["get"]["responses"]["200"]["content"]["application/json"]["schema"]
+}
+
+#[test]
+fn any_of_is_flat_without_duplicates() {
+    let spec = spec_json(SchemaUnions::AnyOf);
+    assert_eq!(
+        schema(&spec, "/pet"),
+        &json!({
+            "anyOf": [
+                { "$ref": "#/components/schemas/Mammal" },
+                { "$ref": "#/components/schemas/Bird" },
+            ]
+        })
+    );
+}
+
+#[test]
+fn one_of_has_discriminator_for_tagged_enums() {
+    let spec = spec_json(SchemaUnions::OneOf);
+    assert_eq!(
+        schema(&spec, "/pet"),
+        &json!({
+            "oneOf": [
+                { "$ref": "#/components/schemas/Mammal" },
+                { "$ref": "#/components/schemas/Bird" },
+            ],
+            "discriminator": {
+                "propertyName": "kind",
+                "mapping": {
+                    "Parrot": "#/components/schemas/Bird",
+                    "Owl": "#/components/schemas/Bird",
+                    "Cat": "#/components/schemas/Mammal",
+                    "Dog": "#/components/schemas/Mammal",
+                }
+            }
+        })
+    );
+}
+
+#[test]
+fn one_of_without_discriminator_for_disjoint_types() {
+    let spec = spec_json(SchemaUnions::OneOf);
+    let schema = schema(&spec, "/plant");
+    assert_eq!(schema["oneOf"].as_array().unwrap().len(), 2);
+    assert!(schema.get("discriminator").is_none());
+}
+
+#[test]
+fn any_of_is_kept_for_overlapping_types() {
+    let spec = spec_json(SchemaUnions::OneOf);
+    // Both are objects, without a shared tag property.
+    let schema = schema(&spec, "/thing");
+    assert_eq!(schema["anyOf"].as_array().unwrap().len(), 2);
+    assert!(schema.get("oneOf").is_none());
+}
+
+#[test]
+fn only_combined_responders_use_one_of() {
+    let spec = spec_json(SchemaUnions::OneOf);
+    // The `anyOf` of `Option<Plant>` is not a combination of responders.
+    let schema = schema(&spec, "/maybe_plant");
+    assert!(schema.get("anyOf").is_some());
+    assert!(schema.get("oneOf").is_none());
+}